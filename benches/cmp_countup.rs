@@ -44,7 +44,7 @@ fn with_flurx(count: usize, c: &mut Criterion) {
             app
                 .add_plugins((
                     TaskPoolPlugin::default(),
-                    FlurxPlugin
+                    FlurxPlugin::default()
                 ))
                 .init_resource::<Exit>()
                 .insert_resource(Limit(count))
@@ -73,7 +73,7 @@ fn with_flurx(repeat: Repeat, count: Count, c: &mut Criterion) {
             app
                 .add_plugins((
                     TaskPoolPlugin::default(),
-                    FlurxPlugin
+                    FlurxPlugin::default()
                 ))
                 .init_resource::<Exit>()
                 .insert_resource(repeat)
@@ -100,12 +100,62 @@ fn with_flurx(repeat: Repeat, count: Count, c: &mut Criterion) {
     });
 }
 
+fn count_up(mut local: Local<usize>, count: Res<Count>) -> bool {
+    *local += 1;
+    let finished = *local == count.0;
+    if finished {
+        *local = 0;
+    }
+    finished
+}
+
+/// Compares the cached system with the system initialized every time the action is created.
+fn with_flurx_cached(repeat: Repeat, count: Count, c: &mut Criterion) {
+    let mut group = c.benchmark_group(format!("with_flurx_cached repeat: {} count: {}", repeat.0, count.0));
+    for cached in [false, true] {
+        group.bench_function(if cached { "until_cached" } else { "until" }, |b| {
+            b.iter(move || {
+                let mut app = App::new();
+                app
+                    .add_plugins((
+                        TaskPoolPlugin::default(),
+                        FlurxPlugin::default()
+                    ))
+                    .init_resource::<Exit>()
+                    .insert_resource(repeat)
+                    .insert_resource(count)
+                    .add_systems(Startup, move |mut commands: Commands| {
+                        commands.spawn(Reactor::schedule(move |task| async move {
+                            let repeat = task.will(Update, once::run(|repeat: Res<Repeat>|repeat.0)).await;
+                            for _ in 0..repeat {
+                                if cached {
+                                    task.will(Update, wait::until_cached(count_up)).await;
+                                } else {
+                                    task.will(Update, wait::until(count_up)).await;
+                                }
+                            }
+                            task.will(Update, once::run(|mut exit: ResMut<Exit>| {
+                                exit.0 = true;
+                            })).await;
+                        }));
+                    });
+
+                while !app.world.resource::<Exit>().0 {
+                    app.update();
+                }
+            });
+        });
+    }
+    group.finish();
+}
+
 fn cmp_repeat_1_count_1000(c: &mut Criterion) {
     const REPEAT: usize = 10;
     const COUNT: usize = 1000;
     
     without_flurx(Repeat(REPEAT), Count(COUNT), c);
     with_flurx(Repeat(REPEAT), Count(COUNT), c);
+    with_flurx_cached(Repeat(REPEAT), Count(COUNT), c);
 }
 
 fn cmp_repeat_1000_count_1(c: &mut Criterion) {
@@ -114,6 +164,7 @@ fn cmp_repeat_1000_count_1(c: &mut Criterion) {
     
     without_flurx(Repeat(REPEAT), Count(COUNT), c);
     with_flurx(Repeat(REPEAT), Count(COUNT), c);
+    with_flurx_cached(Repeat(REPEAT), Count(COUNT), c);
 }
 
 criterion_group!(repeat_countup, cmp_repeat_1_count_1000, cmp_repeat_1000_count_1);
@@ -19,7 +19,7 @@ fn without_sequence(c: &mut Criterion) {
             app
                 .add_plugins((
                     TaskPoolPlugin::default(),
-                    FlurxPlugin
+                    FlurxPlugin::default()
                 ))
                 .init_resource::<Exit>()
                 .add_systems(Startup, |mut commands: Commands| {
@@ -45,7 +45,7 @@ fn with_sequence(c: &mut Criterion) {
             app
                 .add_plugins((
                     TaskPoolPlugin::default(),
-                    FlurxPlugin
+                    FlurxPlugin::default()
                 ))
                 .init_resource::<Exit>()
                 .add_systems(Startup, |mut commands: Commands| {
@@ -0,0 +1,82 @@
+//! Measures the frame time while thousands of tokio effects are running.
+#![allow(missing_docs)]
+
+use bevy::app::App;
+use bevy::core::TaskPoolPlugin;
+use bevy::prelude::Update;
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+
+use bevy_flurx::FlurxPlugin;
+use bevy_flurx::prelude::{effect, FlurxTokioPlugin, Reactor, wait};
+
+const EFFECTS: usize = 5000;
+
+fn new_app(tokio_plugin: bool) -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        TaskPoolPlugin::default(),
+        FlurxPlugin::default(),
+    ));
+    if tokio_plugin {
+        app.add_plugins(FlurxTokioPlugin::default());
+    }
+    app
+}
+
+/// The frame time while the effects are waiting for the tasks that never finish,
+/// compared to the reactors waiting for a condition that is never met.
+fn pending_frame(c: &mut Criterion) {
+    let mut app = new_app(true);
+    for _ in 0..EFFECTS {
+        app.world.spawn(Reactor::schedule(|task| async move {
+            task.will(Update, wait::until(|| false)).await;
+        }));
+    }
+    app.update();
+    c.bench_function(&format!("pending_frame without effects count: {EFFECTS}"), |b| {
+        b.iter(|| app.update());
+    });
+
+    for tokio_plugin in [false, true] {
+        let mut app = new_app(tokio_plugin);
+        for _ in 0..EFFECTS {
+            app.world.spawn(Reactor::schedule(|task| async move {
+                task.will(Update, effect::tokio::spawn(std::future::pending::<()>())).await;
+            }));
+        }
+        app.update();
+        c.bench_function(&format!("pending_frame tokio_plugin: {tokio_plugin} count: {EFFECTS}"), |b| {
+            b.iter(|| app.update());
+        });
+    }
+}
+
+/// The time until all the effects have handed off their outputs.
+fn until_all_finished(c: &mut Criterion) {
+    for tokio_plugin in [false, true] {
+        c.bench_function(&format!("until_all_finished tokio_plugin: {tokio_plugin} count: {EFFECTS}"), |b| {
+            b.iter_batched(
+                || {
+                    let mut app = new_app(tokio_plugin);
+                    for i in 0..EFFECTS {
+                        app.world.spawn(Reactor::schedule(move |task| async move {
+                            task.will(Update, effect::tokio::spawn(async move {
+                                vec![i; 64]
+                            })).await;
+                        }));
+                    }
+                    app
+                },
+                |mut app| {
+                    while app.world.query::<&Reactor>().iter(&app.world).len() != 0 {
+                        app.update();
+                    }
+                },
+                BatchSize::PerIteration,
+            );
+        });
+    }
+}
+
+criterion_group!(tokio_effects, pending_frame, until_all_finished);
+criterion_main!(tokio_effects);
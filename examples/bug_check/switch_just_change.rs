@@ -10,7 +10,7 @@ fn main() {
     App::new()
         .add_plugins((
             DefaultPlugins,
-            FlurxPlugin
+            FlurxPlugin::default()
         ))
         .add_systems(Startup, setup)
         .add_systems(Update, (
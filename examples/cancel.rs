@@ -16,7 +16,7 @@ fn main() {
     App::new()
         .add_plugins((
             DefaultPlugins,
-            FlurxPlugin
+            FlurxPlugin::default()
         ))
         .add_systems(Startup, (
             setup_camera_and_box,
@@ -28,7 +28,7 @@ fn main() {
     App::new()
         .add_plugins((
             DefaultPlugins,
-            FlurxPlugin
+            FlurxPlugin::default()
         ))
         .add_systems(Startup, (
             spawn_reactor,
@@ -27,7 +27,7 @@ fn main() {
         .add_plugins((
             DefaultPlugins,
             EguiPlugin,
-            FlurxPlugin
+            FlurxPlugin::default()
         ))
         .init_resource::<ResponseInfo>()
         .add_event::<RequestGet>()
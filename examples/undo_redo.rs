@@ -36,7 +36,7 @@ fn main() {
         .add_plugins((
             DefaultPlugins,
             EguiPlugin,
-            FlurxPlugin
+            FlurxPlugin::default()
         ))
         .init_resource::<Record<MoveAct>>()
         .add_record_events::<MoveAct>()
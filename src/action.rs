@@ -40,6 +40,8 @@ pub use _tuple::tuple;
 pub use map::Map;
 pub use remake::Remake;
 
+use std::borrow::Cow;
+
 use crate::prelude::ActionSeed;
 use crate::runner::{BoxedRunner, Output};
 
@@ -60,6 +62,8 @@ pub mod record;
 mod _tuple;
 mod map;
 mod remake;
+mod system_cache;
+pub(crate) mod parallel;
 
 
 /// Represents the system passed to [`ReactiveTask`](crate::task::ReactiveTask).
@@ -72,6 +76,15 @@ impl<I1, O1> Action<I1, O1>
         I1: 'static,
         O1: 'static
 {
+    /// Gives this action a debug name.
+    ///
+    /// Please see [`ActionSeed::named`].
+    #[inline]
+    pub fn named(self, name: impl Into<Cow<'static, str>>) -> Action<I1, O1> {
+        let Action(input, seed) = self;
+        seed.named(name).with(input)
+    }
+
     #[inline(always)]
     pub(crate) fn into_runner(self, output: Output<O1>) -> BoxedRunner {
         self.1.create_runner(self.0, output)
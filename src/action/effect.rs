@@ -1,6 +1,7 @@
 //! Convert the operations with side effects such as asynchronous runtime or thread
 //! into the referential-transparent actions.
 
+use std::fmt::{Display, Formatter};
 use std::future::Future;
 
 
@@ -8,8 +9,54 @@ use std::future::Future;
 pub mod tokio;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod thread;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod process;
 pub mod bevy_task;
+pub mod stream;
+
+pub use compute::compute;
+#[cfg(not(target_arch = "wasm32"))]
+pub use compute::compute_blocking;
+
+mod compute;
+
+
+/// The error output by the `try_spawn` actions,
+/// such as [`effect::thread::try_spawn`](crate::prelude::effect::thread::try_spawn), when the work did not produce its output.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum EffectError {
+    /// The work panicked. Contains the panic message.
+    Panicked(String),
+
+    /// The task was aborted, for example because the tokio runtime was shut down.
+    Aborted,
+
+    /// The work was dropped before it ran to completion,
+    /// for example because the pool it was sent to was removed.
+    Cancelled,
+}
+
+impl Display for EffectError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Panicked(message) => write!(f, "the effect panicked: {message}"),
+            Self::Aborted => write!(f, "the effect was aborted"),
+            Self::Cancelled => write!(f, "the effect was cancelled before it completed"),
+        }
+    }
+}
+
+impl std::error::Error for EffectError {}
 
+/// Returns the output, or panics on the main thread with the error
+/// so that the panic can be caught by [`FlurxPlugin::catch_panics`](crate::prelude::FlurxPlugin::catch_panics).
+#[inline]
+pub(crate) fn unwrap_effect<O>(result: Result<O, EffectError>) -> O {
+    match result {
+        Ok(out) => out,
+        Err(error) => panic!("{error}"),
+    }
+}
 
 /// This trait is implemented for functions that return future or future.
 pub trait AsyncFunctor<I, Out, M> {
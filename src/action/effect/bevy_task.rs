@@ -3,12 +3,20 @@
 //! actions
 //!
 //! - [`effect::bevy_task::spawn`](crate::prelude::effect::bevy_task::spawn)
+//! - [`effect::bevy_task::try_spawn`](crate::prelude::effect::bevy_task::try_spawn)
+//! - [`effect::bevy_task::spawn_on`](crate::prelude::effect::bevy_task::spawn_on)
+//! - [`effect::bevy_task::try_spawn_on`](crate::prelude::effect::bevy_task::try_spawn_on)
 //! - [`effect::bevy_task::spawn_detached`](crate::prelude::effect::bevy_task::spawn_detached)
+//! - [`effect::bevy_task::spawn_detached_on`](crate::prelude::effect::bevy_task::spawn_detached_on)
 
 
-pub use _spawn::spawn;
-pub use _spawn_detached::spawn_detached;
+pub use _pool::Pool;
+pub use _spawn::{spawn, spawn_on, try_spawn, try_spawn_on};
+pub(crate) use _spawn::{EffectTask, spawn_task, take_output};
+pub use _spawn_detached::{spawn_detached, spawn_detached_on};
 
+#[path = "bevy_task/pool.rs"]
+mod _pool;
 #[path = "bevy_task/spawn.rs"]
 mod _spawn;
 #[path = "bevy_task/spawn_detached.rs"]
@@ -0,0 +1,52 @@
+use bevy::tasks::{AsyncComputeTaskPool, ComputeTaskPool, IoTaskPool, TaskPool};
+
+/// The bevy task pool that [`effect::bevy_task`](crate::prelude::effect::bevy_task) actions spawn their tasks onto.
+///
+/// Use [`Pool::Io`] for the tasks that mostly wait for file or network io,
+/// so that they do not take the threads from the compute work.
+///
+/// The pool must have been initialized, for example by [`TaskPoolPlugin`](bevy::core::TaskPoolPlugin).
+///
+/// ## Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_flurx::prelude::*;
+/// use bevy_flurx::prelude::effect::bevy_task::Pool;
+///
+/// Reactor::schedule(|task| async move{
+///     let bytes = task.will(Update, effect::bevy_task::spawn_on(Pool::Io, async move{
+///         std::fs::read("level.ron").unwrap_or_default()
+///     })).await;
+/// });
+/// ```
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Pool {
+    /// [`ComputeTaskPool`], which is used by the systems and the parallel queries.
+    Compute,
+
+    /// [`AsyncComputeTaskPool`], for the work that may take more than a frame.
+    ///
+    /// This is the default.
+    #[default]
+    AsyncCompute,
+
+    /// [`IoTaskPool`], for the work that waits for io.
+    Io,
+}
+
+impl Pool {
+    /// Returns the task pool.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pool has not been initialized.
+    #[inline]
+    pub fn task_pool(self) -> &'static TaskPool {
+        match self {
+            Self::Compute => ComputeTaskPool::get(),
+            Self::AsyncCompute => AsyncComputeTaskPool::get(),
+            Self::Io => IoTaskPool::get(),
+        }
+    }
+}
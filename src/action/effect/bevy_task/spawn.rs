@@ -1,10 +1,27 @@
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+
 use bevy::prelude::World;
-use crate::action::effect::AsyncFunctor;
+use futures_lite::FutureExt;
+
+use crate::action::effect::{AsyncFunctor, EffectError, unwrap_effect};
+use crate::action::effect::bevy_task::Pool;
 use crate::prelude::{ActionSeed, CancellationToken, Output, Runner};
+use crate::runner::panic_message;
 
-/// Spawns a future onto the bevy thread pool,
+/// Spawns a future onto [`AsyncComputeTaskPool`](bevy::tasks::AsyncComputeTaskPool),
 /// and then wait until its completed.
 ///
+/// Use [`effect::bevy_task::spawn_on`](crate::prelude::effect::bevy_task::spawn_on) to choose the pool.
+///
+/// ## Completion
+///
+/// The future is spawned when the action is started, and runs on the threads of the pool,
+/// so it does not depend on the pools being ticked by [`TaskPoolPlugin`](bevy::core::TaskPoolPlugin) in [`Last`](bevy::app::Last).
+/// Each time the schedule of the action runs, the runner checks whether the task has finished without blocking the main thread.
+/// The action completes in the first run after the future has completed:
+/// in the same frame if the future completed before the schedule runs, otherwise in a later frame.
+///
 /// ```no_run
 ///
 /// use bevy::prelude::*;
@@ -35,33 +52,170 @@ pub fn spawn<I, Out, Functor, M>(f: Functor) -> ActionSeed<I, Out>
         Out: Send + 'static,
         M: Send + 'static
 {
-    ActionSeed::new(|input, output| {
-        BevyTaskRunner {
-            output,
-            #[cfg(not(target_arch = "wasm32"))]
-            task: bevy::tasks::AsyncComputeTaskPool::get().spawn(f.functor(input)),
-            #[cfg(target_arch = "wasm32")]
-            task: Box::pin(f.functor(input))
-        }
+    spawn_on(Pool::AsyncCompute, f)
+}
+
+/// Spawns a future onto `pool`,
+/// and then wait until its completed.
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_flurx::prelude::*;
+/// use bevy_flurx::prelude::effect::bevy_task::Pool;
+///
+/// Reactor::schedule(|task| async move{
+///     let config = task.will(Update, effect::bevy_task::spawn_on(Pool::Io, |path: String| async move{
+///         std::fs::read_to_string(path).unwrap_or_default()
+///     }).with("config.ron".to_string())).await;
+/// });
+/// ```
+pub fn spawn_on<I, Out, Functor, M>(pool: Pool, f: Functor) -> ActionSeed<I, Out>
+    where
+        I: 'static,
+        Functor: AsyncFunctor<I, Out, M> + 'static,
+        Out: Send + 'static,
+        M: Send + 'static
+{
+    ActionSeed::new(move |input, output| {
+        BevyTaskRunner::new(pool, f.functor(input), output, unwrap_effect)
     })
 }
 
-struct BevyTaskRunner<Out> {
+/// Spawns a future onto the bevy thread pool,
+/// and then wait until its completed.
+///
+/// Unlike [`effect::bevy_task::spawn`](crate::prelude::effect::bevy_task::spawn), which panics on the main thread if the future panics,
+/// this outputs [`EffectError::Panicked`] with the panic message.
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_flurx::prelude::*;
+///
+/// Reactor::schedule(|task| async move{
+///     let result = task.will(Update, effect::bevy_task::try_spawn(|num: usize| async move{
+///         100 / num
+///     }).with(0)).await;
+///     assert!(matches!(result, Err(EffectError::Panicked(_))));
+/// });
+/// ```
+pub fn try_spawn<I, Out, Functor, M>(f: Functor) -> ActionSeed<I, Result<Out, EffectError>>
+    where
+        I: 'static,
+        Functor: AsyncFunctor<I, Out, M> + 'static,
+        Out: Send + 'static,
+        M: Send + 'static
+{
+    try_spawn_on(Pool::AsyncCompute, f)
+}
+
+/// Spawns a future onto `pool`,
+/// and then wait until its completed.
+///
+/// Unlike [`effect::bevy_task::spawn_on`](crate::prelude::effect::bevy_task::spawn_on), which panics on the main thread if the future panics,
+/// this outputs [`EffectError::Panicked`] with the panic message.
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_flurx::prelude::*;
+/// use bevy_flurx::prelude::effect::bevy_task::Pool;
+///
+/// Reactor::schedule(|task| async move{
+///     let result = task.will(Update, effect::bevy_task::try_spawn_on(Pool::Io, async move{
+///         std::fs::read_to_string("config.ron").unwrap()
+///     })).await;
+///     if let Err(error) = result {
+///         error!("{error}");
+///     }
+/// });
+/// ```
+pub fn try_spawn_on<I, Out, Functor, M>(pool: Pool, f: Functor) -> ActionSeed<I, Result<Out, EffectError>>
+    where
+        I: 'static,
+        Functor: AsyncFunctor<I, Out, M> + 'static,
+        Out: Send + 'static,
+        M: Send + 'static
+{
+    ActionSeed::new(move |input, output| {
+        BevyTaskRunner::new(pool, f.functor(input), output, |result| result)
+    })
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) type EffectTask<O> = bevy::tasks::Task<Result<O, EffectError>>;
+#[cfg(target_arch = "wasm32")]
+pub(crate) type EffectTask<O> = std::pin::Pin<Box<dyn Future<Output=Result<O, EffectError>>>>;
+
+/// Spawns `future` onto `pool`,
+/// catching its panic as [`EffectError::Panicked`].
+///
+/// Use [`take_output`] to check for the output.
+pub(crate) fn spawn_task<O>(pool: Pool, future: impl Future<Output=O> + Send + 'static) -> EffectTask<O>
+    where
+        O: Send + 'static
+{
+    let future = async move {
+        AssertUnwindSafe(future)
+            .catch_unwind()
+            .await
+            .map_err(|payload| EffectError::Panicked(panic_message(payload.as_ref())))
+    };
     #[cfg(not(target_arch = "wasm32"))]
-    task: bevy::tasks::Task<Out>,
+    {
+        pool.task_pool().spawn(future)
+    }
     #[cfg(target_arch = "wasm32")]
-    task: std::pin::Pin<Box<dyn std::future::Future<Output=Out>>>,
+    {
+        let _ = pool;
+        Box::pin(future)
+    }
+}
+
+/// Returns the output if the task has finished, without blocking.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn take_output<O>(task: &mut EffectTask<O>) -> Option<Result<O, EffectError>> {
+    // Resolves immediately because the task has finished.
+    task.is_finished().then(|| pollster::block_on(task))
+}
+
+/// Polls the future once, because there are no threads to run it on.
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn take_output<O>(task: &mut EffectTask<O>) -> Option<Result<O, EffectError>> {
+    pollster::block_on(futures_lite::future::poll_once(task))
+}
+
+struct BevyTaskRunner<O, Out> {
+    task: EffectTask<O>,
     output: Output<Out>,
+    finish: fn(Result<O, EffectError>) -> Out,
+}
+
+impl<O, Out> BevyTaskRunner<O, Out>
+    where
+        O: Send + 'static
+{
+    #[inline]
+    fn new(
+        pool: Pool,
+        future: impl Future<Output=O> + Send + 'static,
+        output: Output<Out>,
+        finish: fn(Result<O, EffectError>) -> Out,
+    ) -> Self {
+        Self {
+            task: spawn_task(pool, future),
+            output,
+            finish,
+        }
+    }
 }
 
-impl<Out> Runner for BevyTaskRunner<Out>
+impl<O, Out> Runner for BevyTaskRunner<O, Out>
     where
-        Out: Send + 'static
+        O: Send + 'static,
+        Out: 'static
 {
-    #[allow(clippy::async_yields_async)]
     fn run(&mut self, _: &mut World, _: &CancellationToken) -> bool {
-        if let Some(out) = pollster::block_on(futures_lite::future::poll_once(&mut self.task)) {
-            self.output.set(out);
+        if let Some(result) = take_output(&mut self.task) {
+            self.output.set((self.finish)(result));
             true
         } else {
             false
@@ -69,23 +223,28 @@ impl<Out> Runner for BevyTaskRunner<Out>
     }
 }
 
-
 #[cfg(test)]
 mod tests {
-    use bevy::app::{Startup, Update};
+    use std::sync::{Arc, Mutex};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::{Duration, Instant};
+
+    use bevy::app::{App, Startup, Update};
     use bevy::core::TaskPoolPlugin;
     use bevy::prelude::Commands;
     use bevy_test_helper::resource::count::Count;
     use bevy_test_helper::resource::DirectResourceControl;
+    use futures::channel::oneshot;
 
-    use crate::action::{effect, once};
-    use crate::prelude::{Pipe, Reactor};
+    use crate::action::{effect, once, wait};
+    use crate::action::effect::bevy_task::Pool;
+    use crate::action::effect::{EffectError, unwrap_effect};
+    use crate::action::effect::bevy_task::_spawn::{BevyTaskRunner, spawn_task};
+    use crate::prelude::{ActionSeed, Pipe, Reactor};
+    use crate::test_util::{update_until_finished, update_until_some};
     use crate::tests::test_app;
 
-    //TODO: It fails about once every two times.
-    // Need to check the internal code of `bevy_task` crate.
     #[test]
-    #[ignore]
     fn test_simple_case() {
         let mut app = test_app();
         app.add_plugins(TaskPoolPlugin::default());
@@ -100,8 +259,127 @@ mod tests {
             }));
         });
         app.update();
+        update_until_finished(&mut app);
+        app.assert_resource_eq(Count(2));
+    }
+
+    #[test]
+    fn finish_in_first_frame_if_task_completed() {
+        let mut app = test_app();
+        app.add_plugins(TaskPoolPlugin::default());
+        let task = spawn_task(Pool::AsyncCompute, async move {
+            Count(1 + 1)
+        });
+        let start = Instant::now();
+        while !task.is_finished() {
+            assert!(start.elapsed() < Duration::from_secs(5));
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        app.world.spawn(Reactor::schedule(|t| async move {
+            t.will(Update, ActionSeed::new(move |_, output| BevyTaskRunner {
+                task,
+                output,
+                finish: unwrap_effect,
+            })
+                .pipe(once::res::insert())
+            ).await;
+        }));
+        app.update();
         app.assert_resource_eq(Count(2));
     }
+
+    #[test]
+    fn wait_until_future_completed() {
+        let mut app = test_app();
+        app.add_plugins(TaskPoolPlugin::default());
+        let (sender, receiver) = oneshot::channel::<usize>();
+        app.world.spawn(Reactor::schedule(|task| async move {
+            task.will(Update, {
+                effect::bevy_task::spawn(async move {
+                    Count(receiver.await.unwrap())
+                })
+                    .pipe(once::res::insert())
+            }).await;
+        }));
+        for _ in 0..10 {
+            app.update();
+            app.assert_resource_eq(Count(0));
+        }
+        sender.send(3).unwrap();
+        update_until_finished(&mut app);
+        app.assert_resource_eq(Count(3));
+    }
+
+    #[test]
+    fn output_panic_message() {
+        let mut app = test_app();
+        app.add_plugins(TaskPoolPlugin::default());
+        let result = Arc::new(Mutex::new(None));
+        let out = result.clone();
+        app.world.spawn(Reactor::schedule(|task| async move {
+            let result = task.will(Update, effect::bevy_task::try_spawn(async move {
+                panic!("failed to load");
+            })).await;
+            out.lock().unwrap().replace(result);
+        }));
+        update_until_finished(&mut app);
+        assert_eq!(
+            result.lock().unwrap().take(),
+            Some(Err::<(), _>(EffectError::Panicked("failed to load".to_string())))
+        );
+    }
+
+    #[test]
+    fn drop_task_if_cancelled() {
+        let mut app = test_app();
+        app.add_plugins(TaskPoolPlugin::default());
+        let (sender, receiver) = oneshot::channel::<()>();
+        let completed = Arc::new(AtomicBool::new(false));
+        let c = completed.clone();
+        app.world.spawn(Reactor::schedule(|task| async move {
+            task.will(Update, wait::either(
+                once::run(|| {}),
+                effect::bevy_task::spawn(async move {
+                    let _ = receiver.await;
+                    c.store(true, Ordering::Relaxed);
+                }),
+            )).await;
+        }));
+        update_until_finished(&mut app);
+        // The receiver is dropped along with the task.
+        assert!(sender.send(()).is_err());
+        assert!(!completed.load(Ordering::Relaxed));
+    }
+
+    fn spawn_thread_name_reactor(app: &mut App, pool: Pool) -> Arc<Mutex<Option<String>>> {
+        let name = Arc::new(Mutex::new(None));
+        let out = name.clone();
+        app.world.spawn(Reactor::schedule(move |task| async move {
+            let name = task.will(Update, effect::bevy_task::spawn_on(pool, async move {
+                std::thread::current().name().unwrap_or_default().to_string()
+            })).await;
+            out.lock().unwrap().replace(name);
+        }));
+        name
+    }
+
+    #[test]
+    fn spawn_on_pool() {
+        for (pool, thread_name) in [
+            (Pool::Compute, None),
+            (Pool::AsyncCompute, Some("Async Compute Task Pool")),
+            (Pool::Io, Some("IO Task Pool")),
+        ] {
+            let mut app = test_app();
+            app.add_plugins(TaskPoolPlugin::default());
+            let name = spawn_thread_name_reactor(&mut app, pool);
+            let name = update_until_some(&mut app, &name);
+            // The main thread may also run the tasks of the compute pool while it runs the systems.
+            if let Some(thread_name) = thread_name {
+                assert!(name.starts_with(thread_name), "{pool:?} ran on {name}");
+            }
+        }
+    }
 }
 
 #[cfg(all(test, feature = "tokio"))]
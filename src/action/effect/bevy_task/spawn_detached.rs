@@ -2,14 +2,14 @@ use std::marker::PhantomData;
 use std::sync::{Arc, Mutex};
 
 use bevy::prelude::World;
-use bevy::tasks::AsyncComputeTaskPool;
 
 use crate::action::effect::AsyncFunctor;
+use crate::action::effect::bevy_task::Pool;
 use crate::prelude::{ActionSeed, CancellationToken, Output};
 use crate::runner::Runner;
 
 
-/// Spawns a future onto the bevy thread pool, 
+/// Spawns a future onto [`AsyncComputeTaskPool`](bevy::tasks::AsyncComputeTaskPool),
 /// and then wait until its completed.
 ///
 /// Unlike [`effect::bevy_task::spawn`](crate::prelude::effect::bevy_task::spawn_detached),
@@ -47,8 +47,36 @@ pub fn spawn_detached<I, Out, Functor, M>(functor: Functor) -> ActionSeed<I, Out
         Out: Send + 'static,
         M: Send + 'static
 {
-    ActionSeed::new(|input, output| {
+    spawn_detached_on(Pool::AsyncCompute, functor)
+}
+
+/// Spawns a future onto `pool`,
+/// and then wait until its completed.
+///
+/// Like [`effect::bevy_task::spawn_detached`](crate::prelude::effect::bevy_task::spawn_detached),
+/// the task continues to run even if [`Reactor`](crate::prelude::Reactor) is canceled.
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_flurx::prelude::*;
+/// use bevy_flurx::prelude::effect::bevy_task::Pool;
+///
+/// Reactor::schedule(|task| async move{
+///     task.will(Update, effect::bevy_task::spawn_detached_on(Pool::Io, |log: String| async move{
+///         let _ = std::fs::write("session.log", log);
+///     }).with("finished".to_string())).await;
+/// });
+/// ```
+pub fn spawn_detached_on<I, Out, Functor, M>(pool: Pool, functor: Functor) -> ActionSeed<I, Out>
+    where
+        I: Send + 'static,
+        Functor: AsyncFunctor<I, Out, M> + Send + 'static,
+        Out: Send + 'static,
+        M: Send + 'static
+{
+    ActionSeed::new(move |input, output| {
         BevyDetachedTaskRunner {
+            pool,
             output,
             arc_output: Arc::new(Mutex::new(None)),
             args: Some((input, functor)),
@@ -58,6 +86,7 @@ pub fn spawn_detached<I, Out, Functor, M>(functor: Functor) -> ActionSeed<I, Out
 }
 
 struct BevyDetachedTaskRunner<I, O, Functor, M> {
+    pool: Pool,
     arc_output: Arc<Mutex<Option<O>>>,
     args: Option<(I, Functor)>,
     output: Output<O>,
@@ -74,7 +103,8 @@ impl<I, O, Functor, M> Runner for BevyDetachedTaskRunner<I, O, Functor, M>
     fn run(&mut self, _: &mut World, _: &CancellationToken) -> bool {
         if let Some((input, f)) = self.args.take() {
             let o = self.arc_output.clone();
-            AsyncComputeTaskPool::get()
+            self.pool
+                .task_pool()
                 .spawn(async move {
                     let out = f.functor(input).await;
                     o.lock().unwrap().replace(out);
@@ -0,0 +1,154 @@
+use bevy::ecs::system::ReadOnlySystem;
+use bevy::prelude::IntoSystem;
+
+use crate::action::{effect, once};
+use crate::prelude::{ActionSeed, Pipe};
+
+/// Copies data out of the [`World`](bevy::prelude::World) with `extract`,
+/// runs `work` on [`AsyncComputeTaskPool`](bevy::tasks::AsyncComputeTaskPool) with it,
+/// and then applies the result with `apply`.
+///
+/// `extract` must be a read-only system, and the output of `apply` becomes the output of the action.
+/// The three steps form one action, so cancelling it while `work` is running drops the task and skips `apply`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_flurx::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Path(Vec<Vec3>);
+///
+/// Reactor::schedule(|task| async move{
+///     task.will(Update, effect::compute(
+///         |In(entity): In<Entity>, transforms: Query<&Transform>|{
+///             (entity, transforms.get(entity).unwrap().translation)
+///         },
+///         |(entity, start): (Entity, Vec3)|{
+///             (entity, vec![start, start + Vec3::X])
+///         },
+///         |In((entity, path)): In<(Entity, Vec<Vec3>)>, mut commands: Commands|{
+///             commands.entity(entity).insert(Path(path));
+///         },
+///     ).with(Entity::PLACEHOLDER)).await;
+/// });
+/// ```
+pub fn compute<I, E, W, O, Extract, Apply, M1, M2>(
+    extract: Extract,
+    work: impl FnOnce(E) -> W + Send + 'static,
+    apply: Apply,
+) -> ActionSeed<I, O>
+    where
+        I: 'static,
+        E: Send + 'static,
+        W: Send + 'static,
+        O: 'static,
+        Extract: IntoSystem<I, E, M1> + 'static,
+        Extract::System: ReadOnlySystem,
+        Apply: IntoSystem<W, O, M2> + 'static,
+{
+    once::run(extract)
+        .pipe(effect::bevy_task::spawn(move |extracted: E| async move {
+            work(extracted)
+        }))
+        .pipe(once::run(apply))
+}
+
+/// Copies data out of the [`World`](bevy::prelude::World) with `extract`,
+/// runs `work` on a thread of [`BlockingPool`](crate::prelude::effect::thread::BlockingPool) with it,
+/// and then applies the result with `apply`.
+///
+/// Use this instead of [`effect::compute`](crate::prelude::effect::compute) if `work` blocks,
+/// for example on file io.
+///
+/// # Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_flurx::prelude::*;
+///
+/// #[derive(Resource)]
+/// struct SaveData(String);
+///
+/// Reactor::schedule(|task| async move{
+///     task.will(Update, effect::compute_blocking(
+///         |data: Res<SaveData>| data.0.clone(),
+///         |data: String| std::fs::write("save.ron", data).is_ok(),
+///         |In(saved): In<bool>|{
+///             info!("saved: {saved}");
+///         },
+///     )).await;
+/// });
+/// ```
+#[cfg(not(target_arch = "wasm32"))]
+pub fn compute_blocking<I, E, W, O, Extract, Apply, M1, M2>(
+    extract: Extract,
+    work: impl FnOnce(E) -> W + Send + 'static,
+    apply: Apply,
+) -> ActionSeed<I, O>
+    where
+        I: 'static,
+        E: Send + 'static,
+        W: Send + 'static,
+        O: 'static,
+        Extract: IntoSystem<I, E, M1> + 'static,
+        Extract::System: ReadOnlySystem,
+        Apply: IntoSystem<W, O, M2> + 'static,
+{
+    once::run(extract)
+        .pipe(effect::thread::spawn(work))
+        .pipe(once::run(apply))
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::core::TaskPoolPlugin;
+    use bevy::prelude::{In, Res, ResMut, Resource, Update};
+    use bevy_test_helper::resource::count::Count;
+    use bevy_test_helper::resource::DirectResourceControl;
+
+    use crate::action::effect;
+    use crate::prelude::Reactor;
+    use crate::test_util::update_until_finished;
+    use crate::tests::test_app;
+
+    #[derive(Resource)]
+    struct Source(Vec<usize>);
+
+    #[test]
+    fn extract_compute_apply() {
+        let mut app = test_app();
+        app.add_plugins(TaskPoolPlugin::default());
+        app.insert_resource(Source(vec![1, 2, 3]));
+        app.world.spawn(Reactor::schedule(|task| async move {
+            task.will(Update, effect::compute(
+                |In(scale): In<usize>, source: Res<Source>| (scale, source.0.clone()),
+                |(scale, nums): (usize, Vec<usize>)| nums.iter().sum::<usize>() * scale,
+                |In(sum): In<usize>, mut count: ResMut<Count>| {
+                    count.0 = sum;
+                },
+            ).with(10)).await;
+        }));
+        update_until_finished(&mut app);
+        app.assert_resource_eq(Count(60));
+    }
+
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn extract_compute_apply_on_thread() {
+        let mut app = test_app();
+        app.insert_resource(Source(vec![4, 5]));
+        app.world.spawn(Reactor::schedule(|task| async move {
+            task.will(Update, effect::compute_blocking(
+                |source: Res<Source>| source.0.clone(),
+                |nums: Vec<usize>| nums.iter().product::<usize>(),
+                |In(product): In<usize>, mut count: ResMut<Count>| {
+                    count.0 = product;
+                },
+            )).await;
+        }));
+        update_until_finished(&mut app);
+        app.assert_resource_eq(Count(20));
+    }
+}
@@ -0,0 +1,468 @@
+//! Convert the external processes into [`Action`](crate::prelude::Action).
+//!
+//! actions
+//!
+//! - [`effect::process::output`](crate::prelude::effect::process::output)
+//! - [`effect::process::lines`](crate::prelude::effect::process::lines)
+//!
+//! The process is killed if the action is cancelled.
+//!
+//! The actions stop waiting for stdout and stderr shortly after the process exits,
+//! so a background process that inherited them does not keep the action running.
+//! The threads reading them are left blocked until that process closes them.
+
+use std::io;
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::{Arc, Mutex};
+use std::sync::mpsc::{channel, Receiver, Sender, TryRecvError};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use bevy::prelude::{IntoSystem, System, World};
+
+use crate::action::system_cache::CachedSystem;
+use crate::prelude::{ActionSeed, CancellationToken};
+use crate::runner::{Output, Runner};
+
+/// A line written by the process, without the line terminator.
+///
+/// Invalid UTF-8 is replaced with `U+FFFD`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum ProcessLine {
+    /// A line written to stdout.
+    Stdout(String),
+
+    /// A line written to stderr.
+    Stderr(String),
+}
+
+/// Spawns the process described by the input [`Command`], and then wait until it exits.
+///
+/// The output is its exit status and everything it wrote to stdout and stderr,
+/// or the error if it could not be spawned.
+/// The action completes once the process has exited and its stdout and stderr have been closed,
+/// or shortly after it exits if they are still held open by another process.
+///
+/// The process is spawned when [`Runner`] is executed for the first time,
+/// and its output is read on dedicated threads, so the main thread is never blocked.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::process::Command;
+/// use bevy::prelude::*;
+/// use bevy_flurx::prelude::*;
+///
+/// Reactor::schedule(|task| async move{
+///     let mut command = Command::new("glslc");
+///     command.args(["shader.vert", "-o", "shader.spv"]);
+///     let output = task.will(Update, effect::process::output().with(command)).await;
+///     match output {
+///         Ok(output) if output.status.success() => info!("compiled"),
+///         Ok(output) => error!("{}", String::from_utf8_lossy(&output.stderr)),
+///         Err(error) => error!("failed to run glslc: {error}"),
+///     }
+/// });
+/// ```
+pub fn output() -> ActionSeed<Command, io::Result<std::process::Output>> {
+    ActionSeed::new(|command, output| {
+        OutputRunner {
+            command: Some(command),
+            process: None,
+            output,
+        }
+    })
+}
+
+/// Spawns the process described by the input [`Command`], and then wait until it exits.
+///
+/// Each frame, `progress` is run once for each line written to stdout or stderr since the last frame.
+/// The output is the exit status, or the error if the process could not be spawned.
+/// The action completes once the process has exited and all of its lines have been forwarded,
+/// or shortly after it exits if its stdout and stderr are still held open by another process.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::process::Command;
+/// use bevy::prelude::*;
+/// use bevy_flurx::prelude::*;
+/// use bevy_flurx::prelude::effect::process::ProcessLine;
+///
+/// #[derive(Resource, Default)]
+/// struct BuildLog(Vec<String>);
+///
+/// Reactor::schedule(|task| async move{
+///     let mut command = Command::new("cargo");
+///     command.arg("build");
+///     let status = task.will(Update, effect::process::lines(
+///         |In(line): In<ProcessLine>, mut log: ResMut<BuildLog>|{
+///             match line {
+///                 ProcessLine::Stdout(line) | ProcessLine::Stderr(line) => log.0.push(line),
+///             }
+///         },
+///     ).with(command)).await;
+///     info!("cargo build: {status:?}");
+/// });
+/// ```
+pub fn lines<Sys, M>(progress: Sys) -> ActionSeed<Command, io::Result<ExitStatus>>
+    where
+        Sys: IntoSystem<ProcessLine, (), M> + 'static,
+{
+    ActionSeed::new(move |command, output| {
+        LinesRunner {
+            command: Some(command),
+            process: None,
+            receiver: None,
+            closed: false,
+            system: CachedSystem::new(IntoSystem::into_system(progress)),
+            output,
+        }
+    })
+}
+
+/// How long the pipes are still read after the process exits.
+const DRAIN_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// The child process, which is killed when dropped before it exits.
+struct Process {
+    child: Child,
+    status: Option<ExitStatus>,
+    exited_at: Option<Instant>,
+}
+
+impl Process {
+    fn spawn(command: &mut Command) -> io::Result<Self> {
+        let child = command
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        Ok(Self {
+            child,
+            status: None,
+            exited_at: None,
+        })
+    }
+
+    /// Returns the exit status if the process has exited, without blocking.
+    fn try_status(&mut self) -> io::Result<Option<ExitStatus>> {
+        if self.status.is_none() {
+            self.status = self.child.try_wait()?;
+            if self.status.is_some() {
+                self.exited_at = Some(Instant::now());
+            }
+        }
+        Ok(self.status)
+    }
+
+    /// Returns true if the process exited more than [`DRAIN_TIMEOUT`] ago.
+    fn drained(&self) -> bool {
+        self.exited_at.is_some_and(|exited_at| DRAIN_TIMEOUT <= exited_at.elapsed())
+    }
+}
+
+impl Drop for Process {
+    fn drop(&mut self) {
+        if self.status.is_none() && matches!(self.child.try_wait(), Ok(None)) {
+            let _ = self.child.kill();
+            // Reaps the process; this returns soon after it is killed.
+            let _ = self.child.wait();
+        }
+    }
+}
+
+struct OutputRunner {
+    command: Option<Command>,
+    process: Option<(Process, PipeReader, PipeReader)>,
+    output: Output<io::Result<std::process::Output>>,
+}
+
+impl Runner for OutputRunner {
+    fn run(&mut self, _: &mut World, _: &CancellationToken) -> bool {
+        if let Some(mut command) = self.command.take() {
+            match Process::spawn(&mut command) {
+                Ok(mut process) => {
+                    let stdout = read_to_end(process.child.stdout.take());
+                    let stderr = read_to_end(process.child.stderr.take());
+                    self.process.replace((process, stdout, stderr));
+                }
+                Err(error) => {
+                    self.output.set(Err(error));
+                    return true;
+                }
+            }
+        }
+        let Some((process, stdout, stderr)) = self.process.as_mut() else {
+            return false;
+        };
+        let status = match process.try_status() {
+            Ok(Some(status)) => status,
+            Ok(None) => return false,
+            Err(error) => {
+                self.output.set(Err(error));
+                return true;
+            }
+        };
+        if !(stdout.is_finished() && stderr.is_finished() || process.drained()) {
+            return false;
+        }
+
+        let (_, stdout, stderr) = self.process.take().unwrap();
+        self.output.set(Ok(std::process::Output {
+            status,
+            stdout: stdout.take(),
+            stderr: stderr.take(),
+        }));
+        true
+    }
+}
+
+struct LinesRunner<Sys> {
+    command: Option<Command>,
+    process: Option<Process>,
+    receiver: Option<Receiver<ProcessLine>>,
+    closed: bool,
+    system: CachedSystem<Sys>,
+    output: Output<io::Result<ExitStatus>>,
+}
+
+impl<Sys> Runner for LinesRunner<Sys>
+    where
+        Sys: System<In=ProcessLine, Out=()>,
+{
+    fn run(&mut self, world: &mut World, _: &CancellationToken) -> bool {
+        if let Some(mut command) = self.command.take() {
+            match Process::spawn(&mut command) {
+                Ok(mut process) => {
+                    let (sender, receiver) = channel();
+                    read_lines(process.child.stdout.take(), sender.clone(), ProcessLine::Stdout);
+                    read_lines(process.child.stderr.take(), sender, ProcessLine::Stderr);
+                    self.process.replace(process);
+                    self.receiver.replace(receiver);
+                }
+                Err(error) => {
+                    self.output.set(Err(error));
+                    return true;
+                }
+            }
+        }
+        let (Some(process), Some(receiver)) = (self.process.as_mut(), self.receiver.as_ref()) else {
+            return false;
+        };
+
+        // Checked before receiving so that all the lines written before the process exited are forwarded.
+        let status = process.try_status();
+        let system = self.system.get(world);
+        loop {
+            match receiver.try_recv() {
+                Ok(line) => system.run(line, world),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.closed = true;
+                    break;
+                }
+            }
+        }
+        system.apply_deferred(world);
+
+        let status = match status {
+            Ok(Some(status)) if self.closed || process.drained() => Ok(status),
+            Ok(_) => return false,
+            Err(error) => Err(error),
+        };
+        self.system.release(world);
+        self.output.set(status);
+        true
+    }
+}
+
+/// Reads a pipe on a dedicated thread, keeping what has been read so far.
+struct PipeReader {
+    buf: Arc<Mutex<Vec<u8>>>,
+    handle: JoinHandle<()>,
+}
+
+impl PipeReader {
+    #[inline]
+    fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+
+    fn take(self) -> Vec<u8> {
+        std::mem::take(&mut *self.buf.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+fn read_to_end(pipe: Option<impl Read + Send + 'static>) -> PipeReader {
+    let buf = Arc::new(Mutex::new(Vec::new()));
+    let out = buf.clone();
+    let handle = spawn_reader(move || {
+        let Some(mut pipe) = pipe else {
+            return;
+        };
+        let mut chunk = [0; 4096];
+        loop {
+            match pipe.read(&mut chunk) {
+                Ok(0) => return,
+                Ok(read) => out.lock().unwrap().extend_from_slice(&chunk[..read]),
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(_) => return,
+            }
+        }
+    });
+    PipeReader {
+        buf,
+        handle,
+    }
+}
+
+fn read_lines(
+    pipe: Option<impl Read + Send + 'static>,
+    sender: Sender<ProcessLine>,
+    line: fn(String) -> ProcessLine,
+) {
+    spawn_reader(move || {
+        let Some(pipe) = pipe else {
+            return;
+        };
+        let mut reader = BufReader::new(pipe);
+        let mut buf = Vec::new();
+        while reader.read_until(b'\n', &mut buf).is_ok_and(|read| 0 < read) {
+            let text = String::from_utf8_lossy(&buf);
+            let text = text.trim_end_matches('\n').trim_end_matches('\r');
+            if sender.send(line(text.to_string())).is_err() {
+                return;
+            }
+            buf.clear();
+        }
+    });
+}
+
+fn spawn_reader<T: Send + 'static>(f: impl FnOnce() -> T + Send + 'static) -> JoinHandle<T> {
+    std::thread::Builder::new()
+        .name("flurx-process-reader".to_string())
+        .spawn(f)
+        .expect("failed to spawn a process reader thread")
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::process::{Command, Stdio};
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, Instant};
+
+    use bevy::prelude::{In, Res, ResMut, Resource, Update};
+
+    use crate::action::{effect, wait};
+    use crate::action::effect::process::ProcessLine;
+    use crate::prelude::Reactor;
+    use crate::test_util::update_until_finished;
+    use crate::tests::test_app;
+
+    #[derive(Resource, Default, Debug, Eq, PartialEq)]
+    struct Lines(Vec<ProcessLine>);
+
+    fn sh(script: &str) -> Command {
+        let mut command = Command::new("sh");
+        command.args(["-c", script]);
+        command
+    }
+
+    #[test]
+    fn capture_output() {
+        let mut app = test_app();
+        let result = Arc::new(Mutex::new(None));
+        let out = result.clone();
+        app.world.spawn(Reactor::schedule(|task| async move {
+            let output = task.will(Update, effect::process::output()
+                .with(sh("echo compiled; echo warning >&2; exit 3")),
+            ).await;
+            out.lock().unwrap().replace(output.unwrap());
+        }));
+        update_until_finished(&mut app);
+        let output = result.lock().unwrap().take().unwrap();
+        assert_eq!(output.status.code(), Some(3));
+        assert_eq!(output.stdout, b"compiled\n");
+        assert_eq!(output.stderr, b"warning\n");
+    }
+
+    #[test]
+    fn output_spawn_error() {
+        let mut app = test_app();
+        let result = Arc::new(Mutex::new(None));
+        let out = result.clone();
+        app.world.spawn(Reactor::schedule(|task| async move {
+            let output = task.will(Update, effect::process::output()
+                .with(Command::new("flurx-command-not-exists")),
+            ).await;
+            out.lock().unwrap().replace(output.is_err());
+        }));
+        update_until_finished(&mut app);
+        assert_eq!(result.lock().unwrap().take(), Some(true));
+    }
+
+    #[test]
+    fn forward_lines() {
+        let mut app = test_app();
+        app.init_resource::<Lines>();
+        let result = Arc::new(Mutex::new(None));
+        let out = result.clone();
+        app.world.spawn(Reactor::schedule(|task| async move {
+            let status = task.will(Update, effect::process::lines(
+                |In(line): In<ProcessLine>, mut lines: ResMut<Lines>| {
+                    lines.0.push(line);
+                },
+            ).with(sh("echo 1; echo 2; echo 3 >&2"))).await;
+            out.lock().unwrap().replace(status.unwrap());
+        }));
+        update_until_finished(&mut app);
+        assert!(result.lock().unwrap().take().unwrap().success());
+        let lines = app.world.resource::<Lines>();
+        let stdout: Vec<_> = lines.0.iter().filter(|line| matches!(line, ProcessLine::Stdout(_))).cloned().collect();
+        assert_eq!(stdout, vec![ProcessLine::Stdout("1".to_string()), ProcessLine::Stdout("2".to_string())]);
+        assert!(lines.0.contains(&ProcessLine::Stderr("3".to_string())));
+    }
+
+    #[test]
+    fn kill_process_if_cancelled() {
+        let mut app = test_app();
+        app.init_resource::<Lines>();
+        app.world.spawn(Reactor::schedule(|task| async move {
+            task.will(Update, wait::either(
+                wait::until(|lines: Res<Lines>| !lines.0.is_empty()),
+                effect::process::lines(|In(line): In<ProcessLine>, mut lines: ResMut<Lines>| {
+                    lines.0.push(line);
+                })
+                    .with(sh("echo $$; exec sleep 10")),
+            )).await;
+        }));
+        update_until_finished(&mut app);
+        let lines = &app.world.resource::<Lines>().0;
+        let [ProcessLine::Stdout(pid)] = lines.as_slice() else {
+            panic!("unexpected lines: {lines:?}");
+        };
+        // The process has been killed and reaped when the action is dropped.
+        let alive = Command::new("kill").args(["-0", pid]).stderr(Stdio::null()).status().unwrap();
+        assert!(!alive.success());
+    }
+
+    #[test]
+    fn finish_if_background_process_holds_pipes() {
+        let mut app = test_app();
+        let result = Arc::new(Mutex::new(None));
+        let out = result.clone();
+        app.world.spawn(Reactor::schedule(|task| async move {
+            let output = task.will(Update, effect::process::output()
+                .with(sh("echo done; sleep 10 &")),
+            ).await;
+            out.lock().unwrap().replace(output.unwrap());
+        }));
+        let start = Instant::now();
+        update_until_finished(&mut app);
+        assert!(start.elapsed() < Duration::from_secs(5));
+        let output = result.lock().unwrap().take().unwrap();
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"done\n");
+    }
+}
@@ -0,0 +1,221 @@
+//! Convert the async tasks that report their progress into [`Action`](crate::prelude::Action).
+//!
+//! actions
+//!
+//! - [`effect::stream::spawn`](crate::prelude::effect::stream::spawn)
+//! - [`effect::stream::from_stream`](crate::prelude::effect::stream::from_stream)
+
+use std::future::Future;
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+use bevy::prelude::{IntoSystem, System, World};
+use futures_lite::{Stream, StreamExt};
+
+use crate::action::effect::{AsyncFunctor, unwrap_effect};
+use crate::action::effect::bevy_task::{EffectTask, Pool, spawn_task, take_output};
+use crate::action::system_cache::CachedSystem;
+use crate::prelude::{ActionSeed, CancellationToken};
+use crate::runner::{Output, Runner};
+
+/// Spawns a future onto [`AsyncComputeTaskPool`](bevy::tasks::AsyncComputeTaskPool), passing it [`Sender`] to report progress,
+/// and then wait until its completed.
+///
+/// Each frame, `progress` is run once for each item sent since the last frame,
+/// and the output of the future becomes the output of the action.
+/// The task is dropped if the action is cancelled.
+///
+/// # Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_flurx::prelude::*;
+///
+/// #[derive(Resource, Default)]
+/// struct BakeProgress(f32);
+///
+/// Reactor::schedule(|task| async move{
+///     let baked: usize = task.will(Update, effect::stream::spawn(
+///         |assets: Vec<String>, progress| async move{
+///             for (i, _asset) in assets.iter().enumerate() {
+///                 // bake the asset...
+///                 let _ = progress.send((i + 1) as f32 / assets.len() as f32);
+///             }
+///             assets.len()
+///         },
+///         |In(rate): In<f32>, mut progress: ResMut<BakeProgress>|{
+///             progress.0 = rate;
+///         },
+///     ).with(vec!["player.glb".to_string()])).await;
+/// });
+/// ```
+pub fn spawn<I, P, O, Fut, Sys, M>(
+    f: impl FnOnce(I, Sender<P>) -> Fut + 'static,
+    progress: Sys,
+) -> ActionSeed<I, O>
+    where
+        I: 'static,
+        P: Send + 'static,
+        O: Send + 'static,
+        Fut: Future<Output=O> + Send + 'static,
+        Sys: IntoSystem<P, (), M> + 'static,
+{
+    ActionSeed::new(move |input, output| {
+        let (sender, receiver) = channel();
+        StreamRunner {
+            task: spawn_task(Pool::AsyncCompute, f(input, sender).functor(())),
+            receiver,
+            system: CachedSystem::new(IntoSystem::into_system(progress)),
+            output,
+        }
+    })
+}
+
+/// Polls the stream on the bevy thread pool,
+/// and then wait until it ends.
+///
+/// Each frame, `progress` is run once for each item yielded since the last frame.
+/// The output of the action is the last item, or [`None`] if the stream yielded nothing.
+/// The stream is dropped if the action is cancelled.
+///
+/// # Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_flurx::prelude::*;
+///
+/// #[derive(Resource, Default)]
+/// struct CompressedBytes(usize);
+///
+/// Reactor::schedule(|task| async move{
+///     task.will(Update, effect::stream::from_stream(
+///         |chunks: Vec<Vec<u8>>| futures_lite::stream::iter(chunks.into_iter().map(|chunk| chunk.len())),
+///         |In(len): In<usize>, mut bytes: ResMut<CompressedBytes>|{
+///             bytes.0 += len;
+///         },
+///     ).with(vec![vec![0; 1024]])).await;
+/// });
+/// ```
+pub fn from_stream<I, P, St, Sys, M>(
+    f: impl FnOnce(I) -> St + 'static,
+    progress: Sys,
+) -> ActionSeed<I, Option<P>>
+    where
+        I: 'static,
+        P: Clone + Send + 'static,
+        St: Stream<Item=P> + Send + 'static,
+        Sys: IntoSystem<P, (), M> + 'static,
+{
+    spawn(
+        move |input, sender: Sender<P>| {
+            let stream = f(input);
+            async move {
+                let mut stream = std::pin::pin!(stream);
+                let mut last = None;
+                while let Some(item) = stream.next().await {
+                    let _ = sender.send(item.clone());
+                    last = Some(item);
+                }
+                last
+            }
+        },
+        progress,
+    )
+}
+
+struct StreamRunner<P, O, Sys> {
+    task: EffectTask<O>,
+    receiver: Receiver<P>,
+    system: CachedSystem<Sys>,
+    output: Output<O>,
+}
+
+impl<P, O, Sys> Runner for StreamRunner<P, O, Sys>
+    where
+        P: 'static,
+        O: Send + 'static,
+        Sys: System<In=P, Out=()>,
+{
+    fn run(&mut self, world: &mut World, _: &CancellationToken) -> bool {
+        // Checked before receiving so that all the items sent before the task finished are forwarded.
+        let result = take_output(&mut self.task);
+        let system = self.system.get(world);
+        for item in self.receiver.try_iter() {
+            system.run(item, world);
+        }
+        system.apply_deferred(world);
+
+        if let Some(result) = result {
+            self.system.release(world);
+            self.output.set(unwrap_effect(result));
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::core::TaskPoolPlugin;
+    use bevy::prelude::{In, ResMut, Resource, Update};
+    use bevy_test_helper::resource::count::Count;
+    use bevy_test_helper::resource::DirectResourceControl;
+
+    use crate::action::{effect, once};
+    use crate::prelude::{Pipe, Reactor};
+    use crate::test_util::update_until_finished;
+    use crate::tests::test_app;
+
+    #[derive(Resource, Default, Debug, Eq, PartialEq)]
+    struct Progress(Vec<usize>);
+
+    #[test]
+    fn forward_progress_and_output() {
+        let mut app = test_app();
+        app.add_plugins(TaskPoolPlugin::default());
+        app.init_resource::<Progress>();
+        app.world.spawn(Reactor::schedule(|task| async move {
+            task.will(Update, effect::stream::spawn(
+                |num: usize, sender| async move {
+                    for i in 1..=num {
+                        sender.send(i).unwrap();
+                    }
+                    num * 10
+                },
+                |In(i): In<usize>, mut progress: ResMut<Progress>| {
+                    progress.0.push(i);
+                },
+            )
+                .with(3)
+                .pipe(once::run(|In(num): In<usize>, mut count: ResMut<Count>| {
+                    count.0 = num;
+                })),
+            ).await;
+        }));
+        update_until_finished(&mut app);
+        app.assert_resource_eq(Progress(vec![1, 2, 3]));
+        app.assert_resource_eq(Count(30));
+    }
+
+    #[test]
+    fn forward_stream_items() {
+        let mut app = test_app();
+        app.add_plugins(TaskPoolPlugin::default());
+        app.init_resource::<Progress>();
+        app.world.spawn(Reactor::schedule(|task| async move {
+            task.will(Update, effect::stream::from_stream(
+                |_| futures_lite::stream::iter([4, 5, 6]),
+                |In(i): In<usize>, mut progress: ResMut<Progress>| {
+                    progress.0.push(i);
+                },
+            )
+                .pipe(once::run(|In(last): In<Option<usize>>, mut count: ResMut<Count>| {
+                    count.0 = last.unwrap();
+                })),
+            ).await;
+        }));
+        update_until_finished(&mut app);
+        app.assert_resource_eq(Progress(vec![4, 5, 6]));
+        app.assert_resource_eq(Count(6));
+    }
+}
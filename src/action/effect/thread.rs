@@ -3,20 +3,31 @@
 //! actions
 //! 
 //! - [`effect::thread::spawn`](crate::prelude::effect::thread::spawn)
+//! - [`effect::thread::try_spawn`](crate::prelude::effect::thread::try_spawn)
+//! - [`effect::thread::spawn_cancellable`](crate::prelude::effect::thread::spawn_cancellable)
+//! - [`effect::thread::spawn_pinned`](crate::prelude::effect::thread::spawn_pinned)
 
+use std::borrow::Cow;
+use std::panic::{AssertUnwindSafe, catch_unwind};
 use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use bevy::prelude::World;
 
+use crate::action::effect::{EffectError, unwrap_effect};
 use crate::prelude::{ActionSeed, CancellationToken};
-use crate::runner::{Output, Runner};
+use crate::runner::{Output, panic_message, Runner};
 
+pub use pool::BlockingPool;
 
-/// Spawns a new os thread, and then wait for its output.
+mod pool;
+
+/// Runs `f` on a thread of [`BlockingPool`], and then wait for its output.
 ///
-/// The thread is started when [`Runner`] is executed for the first time.
+/// `f` is dispatched when [`Runner`] is executed for the first time.
 ///
-/// Note that thead created from this function will continue to run even if [`Reactor`](crate::prelude::Reactor) is canceled.
+/// Note that the work started from this function will continue to run even if [`Reactor`](crate::prelude::Reactor) is canceled.
+/// Use [`effect::thread::spawn_cancellable`](crate::prelude::effect::thread::spawn_cancellable) to stop the work.
 /// 
 /// # Examples
 /// 
@@ -45,38 +56,182 @@ pub fn spawn<I, O>(f: impl FnOnce(I) -> O + Send + 'static) -> ActionSeed<I, O>
         O: Send + 'static
 {
     ActionSeed::new(|input, output: Output<O>| {
-        ThreadRunner {
-            arc_output: Arc::new(Mutex::new(None)),
-            args: Some((input, f)),
-            output,
-            handle: None,
-        }
+        ThreadRunner::new(input, f, output, None, CancellationFlag::default(), unwrap_effect)
+    })
+}
+
+/// Runs `f` on a thread of [`BlockingPool`], and then wait for its result.
+///
+/// Unlike [`effect::thread::spawn`](crate::prelude::effect::thread::spawn), which panics on the main thread if `f` panics,
+/// this outputs [`EffectError::Panicked`] with the panic message.
+/// [`EffectError::Cancelled`] is output if [`BlockingPool`] is removed before `f` starts running.
+///
+/// # Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_flurx::prelude::*;
+///
+/// Reactor::schedule(|task| async move{
+///     let result = task.will(Update, effect::thread::try_spawn(|path: String|{
+///         std::fs::read_to_string(path).unwrap()
+///     }).with("settings.ron".to_string())).await;
+///     if let Err(EffectError::Panicked(message)) = result {
+///         error!("failed to load the settings: {message}");
+///     }
+/// });
+/// ```
+pub fn try_spawn<I, O>(f: impl FnOnce(I) -> O + Send + 'static) -> ActionSeed<I, Result<O, EffectError>>
+    where
+        I: Send + 'static,
+        O: Send + 'static
+{
+    ActionSeed::new(|input, output| {
+        ThreadRunner::new(input, f, output, None, CancellationFlag::default(), |result| result)
     })
 }
 
-struct ThreadRunner<I, O, F> {
-    arc_output: Arc<Mutex<Option<O>>>,
+/// Runs `f` on a thread of [`BlockingPool`] so that it can be stopped cooperatively, and then wait for its output.
+///
+/// [`CancellationFlag`] passed to `f` is set when the action is cancelled,
+/// for example when [`Reactor`](crate::prelude::Reactor) is cancelled or another action wins a race such as [`wait::either`](crate::prelude::wait::either).
+/// `f` should check it regularly and return early once it is set; its output is then discarded.
+///
+/// # Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_flurx::prelude::*;
+///
+/// Reactor::schedule(|task| async move{
+///     task.will(Update, effect::thread::spawn_cancellable(|_, flag: effect::thread::CancellationFlag|{
+///         let mut chunks = Vec::new();
+///         for chunk in 0..1000 {
+///             if flag.is_cancelled() {
+///                 break;
+///             }
+///             chunks.push(chunk);
+///         }
+///         chunks
+///     })).await;
+/// });
+/// ```
+pub fn spawn_cancellable<I, O>(f: impl FnOnce(I, CancellationFlag) -> O + Send + 'static) -> ActionSeed<I, O>
+    where
+        I: Send + 'static,
+        O: Send + 'static
+{
+    ActionSeed::new(|input, output: Output<O>| {
+        let flag = CancellationFlag::default();
+        let thread_flag = flag.clone();
+        ThreadRunner::new(input, move |input| f(input, thread_flag), output, None, flag, unwrap_effect)
+    })
+}
+
+/// Runs `f` on the dedicated thread named `thread_name`, and then wait for its output.
+///
+/// The thread is spawned by [`BlockingPool`] the first time the name is used, and all jobs with the same name run on it in order.
+/// This is useful for libraries that must always be called from the same thread.
+///
+/// # Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_flurx::prelude::*;
+///
+/// Reactor::schedule(|task| async move{
+///     let name = task.will(Update, effect::thread::spawn_pinned("ffi", |_| {
+///         std::thread::current().name().map(String::from)
+///     })).await;
+///     assert_eq!(name.as_deref(), Some("ffi"));
+/// });
+/// ```
+pub fn spawn_pinned<I, O>(
+    thread_name: impl Into<Cow<'static, str>>,
+    f: impl FnOnce(I) -> O + Send + 'static,
+) -> ActionSeed<I, O>
+    where
+        I: Send + 'static,
+        O: Send + 'static
+{
+    let thread_name = thread_name.into();
+    ActionSeed::new(|input, output: Output<O>| {
+        ThreadRunner::new(input, f, output, Some(thread_name), CancellationFlag::default(), unwrap_effect)
+    })
+}
+
+/// The flag that tells the thread spawned by [`effect::thread::spawn_cancellable`](crate::prelude::effect::thread::spawn_cancellable)
+/// that its action has been cancelled.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationFlag(Arc<AtomicBool>);
+
+impl CancellationFlag {
+    /// Returns `true` if the action has been cancelled.
+    #[inline]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+
+    #[inline]
+    fn cancel(&self) {
+        self.0.store(true, Ordering::Release);
+    }
+}
+
+struct ThreadRunner<I, O, F, Out> {
+    arc_output: Arc<Mutex<Option<Result<O, EffectError>>>>,
     args: Option<(I, F)>,
-    output: Output<O>,
-    handle: Option<std::thread::JoinHandle<()>>,
+    output: Output<Out>,
+    thread: Option<Cow<'static, str>>,
+    flag: CancellationFlag,
+    finish: fn(Result<O, EffectError>) -> Out,
 }
 
-impl<I, O, F> Runner for ThreadRunner<I, O, F>
+impl<I, O, F, Out> ThreadRunner<I, O, F, Out> {
+    #[inline]
+    fn new(
+        input: I,
+        f: F,
+        output: Output<Out>,
+        thread: Option<Cow<'static, str>>,
+        flag: CancellationFlag,
+        finish: fn(Result<O, EffectError>) -> Out,
+    ) -> Self {
+        Self {
+            arc_output: Arc::new(Mutex::new(None)),
+            args: Some((input, f)),
+            output,
+            thread,
+            flag,
+            finish,
+        }
+    }
+}
+
+impl<I, O, F, Out> Runner for ThreadRunner<I, O, F, Out>
     where
         I: Send + 'static,
         O: Send + 'static,
-        F: FnOnce(I) -> O + Send + 'static
+        F: FnOnce(I) -> O + Send + 'static,
+        Out: 'static
 {
-    fn run(&mut self, _: &mut World, _: &CancellationToken) -> bool {
+    fn run(&mut self, world: &mut World, _: &CancellationToken) -> bool {
         if let Some((input, f)) = self.args.take() {
-            let arc_out = self.arc_output.clone();
-            self.handle.replace(std::thread::spawn(move || {
-                arc_out.lock().unwrap().replace(f(input));
-            }));
+            let slot = ResultSlot(Some(self.arc_output.clone()));
+            let job = move || {
+                slot.set(catch_unwind(AssertUnwindSafe(|| f(input)))
+                    .map_err(|payload| EffectError::Panicked(panic_message(payload.as_ref()))));
+            };
+            let pool = world.get_resource_or_insert_with(BlockingPool::default);
+            match self.thread.take() {
+                Some(thread_name) => pool.execute_on(thread_name, job),
+                None => pool.execute(job),
+            }
         }
 
-        if let Some(out) = self.arc_output.lock().unwrap().take() {
-            self.output.set(out);
+        let result = self.arc_output.lock().unwrap().take();
+        if let Some(result) = result {
+            self.output.set((self.finish)(result));
             true
         } else {
             false
@@ -84,16 +239,51 @@ impl<I, O, F> Runner for ThreadRunner<I, O, F>
     }
 }
 
+impl<I, O, F, Out> Drop for ThreadRunner<I, O, F, Out> {
+    fn drop(&mut self) {
+        // If the runner has finished, the thread has already returned and the flag is no longer read.
+        self.flag.cancel();
+    }
+}
+
+/// Stores the result of the job, or [`EffectError::Cancelled`] if the job is dropped without being run.
+struct ResultSlot<O>(Option<Arc<Mutex<Option<Result<O, EffectError>>>>>);
+
+impl<O> ResultSlot<O> {
+    fn set(mut self, result: Result<O, EffectError>) {
+        if let Some(slot) = self.0.take() {
+            slot.lock().unwrap().replace(result);
+        }
+    }
+}
+
+impl<O> Drop for ResultSlot<O> {
+    fn drop(&mut self) {
+        if let Some(slot) = self.0.take() {
+            if let Ok(mut slot) = slot.lock() {
+                slot.replace(Err(EffectError::Cancelled));
+            }
+        }
+    }
+}
 
 #[cfg(test)]
 mod tests {
-    use bevy::prelude::{Commands, In, ResMut, Startup, Update};
+    use std::sync::{Arc, Mutex};
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::time::{Duration, Instant};
+
+    use bevy::prelude::{Commands, default, In, ResMut, Startup, Update};
     use bevy_test_helper::resource::count::Count;
     use bevy_test_helper::resource::DirectResourceControl;
 
     use crate::action::{effect, once};
-    use crate::prelude::{Pipe, Reactor};
-    use crate::tests::test_app;
+    use crate::action::effect::EffectError;
+    use crate::action::effect::thread::BlockingPool;
+    use crate::FlurxPlugin;
+    use crate::prelude::{Pipe, Reactor, ReactorFailed};
+    use crate::test_util::{update_until, update_until_finished, update_until_some};
+    use crate::tests::{came_event, test_app, test_app_with};
 
     #[test]
     fn thread_calc_2() {
@@ -115,4 +305,176 @@ mod tests {
         app.update();
         app.assert_resource_eq(Count(2));
     }
+
+    #[test]
+    fn stop_thread_after_cancelled() {
+        let mut app = test_app();
+        let stopped = Arc::new(AtomicBool::new(false));
+        let thread_stopped = stopped.clone();
+        let reactor = app.world.spawn(Reactor::schedule(|task| async move {
+            task.will(Update, effect::thread::spawn_cancellable(move |_, flag| {
+                while !flag.is_cancelled() {
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+                thread_stopped.store(true, Ordering::Release);
+            })).await;
+        })).id();
+        app.update();
+        std::thread::sleep(Duration::from_millis(10));
+        assert!(!stopped.load(Ordering::Acquire));
+
+        app.world.despawn(reactor);
+        app.update();
+        let start = Instant::now();
+        while !stopped.load(Ordering::Acquire) {
+            assert!(start.elapsed() < Duration::from_secs(5));
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn run_on_pool_threads() {
+        let mut app = test_app();
+        app.insert_resource(BlockingPool::new(2).thread_name("test-pool"));
+        let names = Arc::new(Mutex::new(Vec::new()));
+        for _ in 0..10 {
+            let names = names.clone();
+            app.world.spawn(Reactor::schedule(|task| async move {
+                let name = task.will(Update, effect::thread::spawn(|_| {
+                    std::thread::current().name().map(String::from)
+                })).await;
+                names.lock().unwrap().push(name);
+            }));
+        }
+        update_until_finished(&mut app);
+        for name in names.lock().unwrap().iter() {
+            let name = name.as_deref().unwrap();
+            assert!(name.starts_with("test-pool-"), "{name}");
+        }
+    }
+
+    #[test]
+    fn grow_pool_while_all_threads_wait() {
+        let mut app = test_app();
+        app.insert_resource(BlockingPool::new(1));
+        let jobs = std::thread::available_parallelism().map_or(4, |n| n.get()) + 2;
+        let arrived = Arc::new(AtomicUsize::new(0));
+        let results = Arc::new(Mutex::new(Vec::new()));
+        for _ in 0..jobs {
+            let arrived = arrived.clone();
+            let results = results.clone();
+            app.world.spawn(Reactor::schedule(move |task| async move {
+                // Each job waits until all the other jobs are running.
+                let all_arrived = task.will(Update, effect::thread::spawn(move |_| {
+                    arrived.fetch_add(1, Ordering::AcqRel);
+                    let start = Instant::now();
+                    while arrived.load(Ordering::Acquire) < jobs {
+                        if Duration::from_secs(5) < start.elapsed() {
+                            return false;
+                        }
+                        std::thread::sleep(Duration::from_millis(1));
+                    }
+                    true
+                })).await;
+                results.lock().unwrap().push(all_arrived);
+            }));
+        }
+        update_until_finished(&mut app);
+        assert!(results.lock().unwrap().iter().all(|all_arrived| *all_arrived));
+    }
+
+    #[test]
+    fn cancel_queued_job_if_pool_removed() {
+        let mut app = test_app();
+        app.insert_resource(BlockingPool::new(1).max_threads(1));
+        let release = Arc::new(AtomicBool::new(false));
+        let blocker_release = release.clone();
+        let result = Arc::new(Mutex::new(None));
+        let out = result.clone();
+        app.world.spawn(Reactor::schedule(|task| async move {
+            // Occupies the only thread so that the next job stays queued.
+            task.will(Update, effect::thread::try_spawn(move |_| {
+                while !blocker_release.load(Ordering::Acquire) {
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+            })).await.ok();
+        }));
+        app.world.spawn(Reactor::schedule(|task| async move {
+            let result = task.will(Update, effect::thread::try_spawn(|_| 1)).await;
+            out.lock().unwrap().replace(result);
+        }));
+        app.update();
+        app.world.remove_resource::<BlockingPool>();
+        release.store(true, Ordering::Release);
+        assert_eq!(update_until_some(&mut app, &result), Err(EffectError::Cancelled));
+    }
+
+    #[test]
+    fn run_on_pinned_thread() {
+        let mut app = test_app();
+        let ids = Arc::new(Mutex::new(Vec::new()));
+        for _ in 0..3 {
+            let ids = ids.clone();
+            app.world.spawn(Reactor::schedule(|task| async move {
+                let (name, id) = task.will(Update, effect::thread::spawn_pinned("pinned", |_| {
+                    let current = std::thread::current();
+                    (current.name().map(String::from), current.id())
+                })).await;
+                assert_eq!(name.as_deref(), Some("pinned"));
+                ids.lock().unwrap().push(id);
+            }));
+        }
+        update_until_finished(&mut app);
+        let ids = ids.lock().unwrap();
+        assert!(ids.iter().all(|id| id == &ids[0]));
+    }
+
+    #[test]
+    fn output_panic_message() {
+        let mut app = test_app();
+        let result = Arc::new(Mutex::new(None));
+        let out = result.clone();
+        app.world.spawn(Reactor::schedule(|task| async move {
+            let result = task.will(Update, effect::thread::try_spawn(|_| {
+                panic!("failed to build mesh");
+            })).await;
+            out.lock().unwrap().replace(result);
+        }));
+        assert_eq!(
+            update_until_some(&mut app, &result),
+            Err::<(), _>(EffectError::Panicked("failed to build mesh".to_string()))
+        );
+    }
+
+    #[test]
+    fn fail_reactor_if_thread_panicked() {
+        let mut app = test_app_with(FlurxPlugin {
+            catch_panics: true,
+            ..default()
+        });
+        app.world.spawn(Reactor::schedule(|task| async move {
+            task.will(Update, effect::thread::spawn(|_| {
+                panic!("failed to build mesh");
+            })).await;
+        }));
+        update_until(&mut app, came_event::<ReactorFailed>);
+    }
+
+    #[test]
+    fn not_cancel_finished_thread() {
+        let mut app = test_app();
+        app.world.spawn(Reactor::schedule(|task| async move {
+            let cancelled = task.will(Update, effect::thread::spawn_cancellable(|_, flag| {
+                flag.is_cancelled()
+            })).await;
+            task.will(Update, once::run(move |mut count: ResMut<Count>| {
+                count.0 = if cancelled { 2 } else { 1 };
+            })).await;
+        }));
+        app.update();
+        std::thread::sleep(Duration::from_millis(10));
+        app.update();
+        app.update();
+        app.assert_resource_eq(Count(1));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,223 @@
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::num::NonZeroUsize;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::sync::mpsc::{channel, Sender};
+use std::time::Duration;
+
+use bevy::prelude::Resource;
+use bevy::utils::HashMap;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// The pool of os threads that [`effect::thread`](crate::prelude::effect::thread) actions run on.
+///
+/// The threads are reused across actions, so running many small blocking jobs does not pay for thread creation each time.
+/// If this resource does not exist when an action first needs it, it is inserted with the default settings.
+///
+/// The pool keeps [`BlockingPool::num_threads`] threads alive.
+/// When all the threads are busy, another thread is spawned for the new job, up to [`BlockingPool::max_threads`],
+/// and the extra threads exit after being idle for [`BlockingPool::keep_alive`].
+/// This way, jobs that wait on each other, such as loops in [`effect::thread::spawn_cancellable`](crate::prelude::effect::thread::spawn_cancellable),
+/// do not starve the jobs they wait on.
+/// Once [`BlockingPool::max_threads`] threads are busy, the new jobs wait for a thread to be free,
+/// so jobs waiting on each other beyond that number can deadlock.
+///
+/// Dedicated threads used by [`effect::thread::spawn_pinned`](crate::prelude::effect::thread::spawn_pinned)
+/// are spawned the first time their name is used and are kept for the lifetime of this resource.
+///
+/// Once this resource is removed, the jobs that have not started yet are dropped,
+/// and the threads exit after their running jobs have finished.
+///
+/// ## Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_flurx::prelude::*;
+///
+/// App::new()
+///     .add_plugins((
+///         DefaultPlugins,
+///         FlurxPlugin::default(),
+///     ))
+///     .insert_resource(effect::thread::BlockingPool::new(4).max_threads(16).thread_name("mesh-builder"));
+/// ```
+#[derive(Resource)]
+pub struct BlockingPool {
+    thread_name: Cow<'static, str>,
+    num_threads: usize,
+    max_threads: usize,
+    keep_alive: Duration,
+    shared: Arc<Shared>,
+    dedicated: Mutex<HashMap<Cow<'static, str>, Sender<Job>>>,
+}
+
+struct Shared {
+    queue: Mutex<Queue>,
+    available: Condvar,
+}
+
+#[derive(Default)]
+struct Queue {
+    jobs: VecDeque<Job>,
+    /// The number of live threads.
+    threads: usize,
+    /// The number of threads waiting for a job.
+    idle: usize,
+    /// The number of threads spawned so far, used for their names.
+    spawned: usize,
+    closed: bool,
+}
+
+impl BlockingPool {
+    /// Creates a pool that keeps `num_threads` threads alive.
+    ///
+    /// The threads are spawned the first time they are needed.
+    /// The default maximum number of threads is the larger of `num_threads` and 512.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num_threads` is 0.
+    pub fn new(num_threads: usize) -> Self {
+        assert!(0 < num_threads, "the pool needs at least one thread");
+        Self {
+            thread_name: Cow::Borrowed("flurx-blocking"),
+            num_threads,
+            max_threads: num_threads.max(512),
+            keep_alive: Duration::from_secs(10),
+            shared: Arc::new(Shared {
+                queue: Mutex::new(Queue::default()),
+                available: Condvar::new(),
+            }),
+            dedicated: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Sets the prefix of the thread names.
+    ///
+    /// The threads are named `{name}-{index}`. The default is `flurx-blocking`.
+    #[inline]
+    pub fn thread_name(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.thread_name = name.into();
+        self
+    }
+
+    /// Sets the maximum number of threads in the pool, not counting the dedicated threads.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_threads` is less than [`BlockingPool::num_threads`].
+    #[inline]
+    pub fn max_threads(mut self, max_threads: usize) -> Self {
+        assert!(self.num_threads <= max_threads, "`max_threads` must not be less than `num_threads`");
+        self.max_threads = max_threads;
+        self
+    }
+
+    /// Sets how long the threads spawned beyond [`BlockingPool::num_threads`] wait for a new job before exiting.
+    ///
+    /// The default is 10 seconds.
+    #[inline]
+    pub fn keep_alive(mut self, keep_alive: Duration) -> Self {
+        self.keep_alive = keep_alive;
+        self
+    }
+
+    /// Returns the number of threads kept alive in the pool, not counting the dedicated threads.
+    #[inline]
+    pub fn num_threads(&self) -> usize {
+        self.num_threads
+    }
+
+    pub(crate) fn execute(&self, job: impl FnOnce() + Send + 'static) {
+        let mut queue = lock(&self.shared.queue);
+        queue.jobs.push_back(Box::new(job));
+        if queue.idle < queue.jobs.len() && queue.threads < self.max_threads {
+            queue.threads += 1;
+            let name = format!("{}-{}", self.thread_name, queue.spawned);
+            queue.spawned += 1;
+            let shared = self.shared.clone();
+            let num_threads = self.num_threads;
+            let keep_alive = self.keep_alive;
+            spawn_worker(name, move || next_job(&shared, num_threads, keep_alive));
+        } else {
+            self.shared.available.notify_one();
+        }
+    }
+
+    pub(crate) fn execute_on(&self, thread_name: Cow<'static, str>, job: impl FnOnce() + Send + 'static) {
+        let mut dedicated = lock(&self.dedicated);
+        let sender = dedicated.entry(thread_name).or_insert_with_key(|name| {
+            let (sender, receiver) = channel::<Job>();
+            let shared = self.shared.clone();
+            // Dropping the receiver drops the jobs left in the channel.
+            spawn_worker(name.to_string(), move || receiver.recv().ok().filter(|_| !lock(&shared.queue).closed));
+            sender
+        });
+        let _ = sender.send(Box::new(job));
+    }
+}
+
+impl Default for BlockingPool {
+    /// Creates a pool that keeps as many threads alive as the available parallelism.
+    fn default() -> Self {
+        Self::new(std::thread::available_parallelism().map_or(4, NonZeroUsize::get))
+    }
+}
+
+impl Drop for BlockingPool {
+    fn drop(&mut self) {
+        let jobs = {
+            let mut queue = lock(&self.shared.queue);
+            queue.closed = true;
+            std::mem::take(&mut queue.jobs)
+        };
+        self.shared.available.notify_all();
+        drop(jobs);
+    }
+}
+
+/// Waits for the next job of the pool.
+///
+/// Returns `None` if the thread should exit.
+fn next_job(shared: &Shared, num_threads: usize, keep_alive: Duration) -> Option<Job> {
+    let mut queue = lock(&shared.queue);
+    loop {
+        if let Some(job) = queue.jobs.pop_front() {
+            return Some(job);
+        }
+        if queue.closed {
+            queue.threads -= 1;
+            return None;
+        }
+        queue.idle += 1;
+        let (guard, timeout) = shared
+            .available
+            .wait_timeout(queue, keep_alive)
+            .unwrap_or_else(PoisonError::into_inner);
+        queue = guard;
+        queue.idle -= 1;
+        if timeout.timed_out() && queue.jobs.is_empty() && num_threads < queue.threads {
+            queue.threads -= 1;
+            return None;
+        }
+    }
+}
+
+#[inline]
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn spawn_worker(name: String, mut next_job: impl FnMut() -> Option<Job> + Send + 'static) {
+    std::thread::Builder::new()
+        .name(name)
+        .spawn(move || {
+            while let Some(job) = next_job() {
+                // A panicking job must not take the worker down with it.
+                let _ = catch_unwind(AssertUnwindSafe(job));
+            }
+        })
+        .expect("failed to spawn a blocking pool thread");
+}
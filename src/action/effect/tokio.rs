@@ -3,22 +3,31 @@
 //! action
 //!
 //! - [`effect::tokio::spawn`](crate::prelude::effect::tokio::spawn)
+//! - [`effect::tokio::try_spawn`](crate::prelude::effect::tokio::try_spawn)
+//!
+//! The tasks are spawned onto [`TokioRuntime`] if [`FlurxTokioPlugin`] has been added.
 
 
 use std::marker::PhantomData;
-use std::sync::Arc;
 
 use async_compat::CompatExt;
 use bevy::prelude::World;
+use tokio::sync::oneshot;
+use tokio::sync::oneshot::error::TryRecvError;
 use tokio::task::JoinHandle;
 
-use crate::action::effect::AsyncFunctor;
+use crate::action::effect::{AsyncFunctor, EffectError, unwrap_effect};
 use crate::prelude::{ActionSeed, CancellationToken};
-use crate::runner::{Output, Runner};
+use crate::runner::{Output, panic_message, Runner};
+
+pub use runtime::{FlurxTokioPlugin, TokioRuntime};
+
+mod runtime;
 
 /// Spawns a new tokio task, and then wait its output.
 ///
 /// The task is started when [`Runner`] is executed for the first time.
+/// It is spawned onto [`TokioRuntime`] if it exists.
 ///
 /// # Example
 ///
@@ -47,54 +56,123 @@ pub fn spawn<I, Out, Functor, M>(f: Functor) -> ActionSeed<I, Out>
         Functor: AsyncFunctor<I, Out, M> + Send + 'static,
 {
     ActionSeed::new(|input: I, output: Output<Out>| {
-        TokioRunner {
-            arc_output: Arc::new(tokio::sync::Mutex::new(None)),
-            args: Some((input, f)),
-            output,
-            handle: None,
-            _m: PhantomData,
-        }
+        TokioRunner::new(input, f, output, unwrap_effect)
     })
 }
 
-struct TokioRunner<I, Out, Functor, M>
+/// Spawns a new tokio task, and then wait its result.
+///
+/// Unlike [`effect::tokio::spawn`](crate::prelude::effect::tokio::spawn), which panics on the main thread if the task panics,
+/// this outputs [`EffectError::Panicked`] with the panic message,
+/// or [`EffectError::Aborted`] if the task is aborted, for example because the runtime was shut down.
+///
+/// # Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_flurx::prelude::*;
+///
+/// Reactor::schedule(|task| async move{
+///     let result = task.will(Update, effect::tokio::try_spawn(async move{
+///         tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+///     })).await;
+///     if let Err(error) = result {
+///         error!("{error}");
+///     }
+/// });
+/// ```
+pub fn try_spawn<I, Out, Functor, M>(f: Functor) -> ActionSeed<I, Result<Out, EffectError>>
+    where
+        I: Send + 'static,
+        M: Send + 'static,
+        Out: Send + 'static,
+        Functor: AsyncFunctor<I, Out, M> + Send + 'static,
+{
+    ActionSeed::new(|input: I, output| {
+        TokioRunner::new(input, f, output, |result| result)
+    })
+}
+
+struct TokioRunner<I, O, Functor, M, Out>
 
 {
     args: Option<(I, Functor)>,
-    arc_output: Arc<tokio::sync::Mutex<Option<Out>>>,
+    receiver: Option<oneshot::Receiver<O>>,
     output: Output<Out>,
     handle: Option<JoinHandle<()>>,
+    finish: fn(Result<O, EffectError>) -> Out,
     _m: PhantomData<M>,
 }
 
-impl<I, Out, Functor, M> Runner for TokioRunner<I, Out, Functor, M>
+impl<I, O, Functor, M, Out> TokioRunner<I, O, Functor, M, Out> {
+    #[inline]
+    fn new(input: I, f: Functor, output: Output<Out>, finish: fn(Result<O, EffectError>) -> Out) -> Self {
+        Self {
+            receiver: None,
+            args: Some((input, f)),
+            output,
+            handle: None,
+            finish,
+            _m: PhantomData,
+        }
+    }
+
+    /// Outputs the reason the task ended without sending its output.
+    ///
+    /// Waits until the next frame if the task has not finished unwinding yet.
+    fn try_output_error(&mut self) -> bool {
+        if self.handle.as_ref().is_some_and(JoinHandle::is_finished) {
+            let handle = self.handle.take().unwrap();
+            let error = match pollster::block_on(handle) {
+                Err(error) if error.is_panic() => EffectError::Panicked(panic_message(error.into_panic().as_ref())),
+                _ => EffectError::Aborted,
+            };
+            self.output.set((self.finish)(Err(error)));
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl<I, O, Functor, M, Out> Runner for TokioRunner<I, O, Functor, M, Out>
     where
         I: Send + 'static,
-        Functor: AsyncFunctor<I, Out, M> + Send + 'static,
+        Functor: AsyncFunctor<I, O, M> + Send + 'static,
         M: Send + 'static,
-        Out: Send + 'static
+        O: Send + 'static,
+        Out: 'static
 {
     #[allow(clippy::async_yields_async)]
-    fn run(&mut self, _: &mut World, _: &CancellationToken) -> bool {
+    fn run(&mut self, world: &mut World, _: &CancellationToken) -> bool {
         if let Some((input, functor)) = self.args.take() {
-            let arc_output = self.arc_output.clone();
-            self.handle.replace(pollster::block_on(async move {
-                tokio::spawn(async move {
-                    arc_output.lock().await.replace(functor.functor(input).await);
-                })
-            }.compat()));
+            let (sender, receiver) = oneshot::channel();
+            self.receiver.replace(receiver);
+            let task = async move {
+                let _ = sender.send(functor.functor(input).await);
+            };
+            self.handle.replace(match world.get_resource::<TokioRuntime>() {
+                Some(runtime) => runtime.handle().spawn(task),
+                None => pollster::block_on(async move { tokio::spawn(task) }.compat()),
+            });
         }
 
-        if let Some(out) = self.arc_output.blocking_lock().take() {
-            self.output.set(out);
-            true
-        } else {
-            false
+        // Never blocks; the sender is dropped without sending only if the task panicked or was aborted.
+        let Some(receiver) = self.receiver.as_mut() else {
+            return false;
+        };
+        match receiver.try_recv() {
+            Ok(out) => {
+                self.output.set((self.finish)(Ok(out)));
+                true
+            }
+            Err(TryRecvError::Closed) => self.try_output_error(),
+            Err(TryRecvError::Empty) => false,
         }
     }
 }
 
-impl<I, Out, Functor, M> Drop for TokioRunner<I, Out, Functor, M> {
+impl<I, O, Functor, M, Out> Drop for TokioRunner<I, O, Functor, M, Out> {
     fn drop(&mut self) {
         if let Some(handle) = self.handle.take() {
             handle.abort();
@@ -102,9 +180,9 @@ impl<I, Out, Functor, M> Drop for TokioRunner<I, Out, Functor, M> {
     }
 }
 
-
 #[cfg(test)]
 mod tests {
+    use std::sync::{Arc, Mutex};
     use std::sync::atomic::{AtomicBool, Ordering};
     use std::time::Duration;
 
@@ -115,8 +193,10 @@ mod tests {
     use bevy_test_helper::resource::DirectResourceControl;
 
     use crate::action::{delay, effect, once, wait};
+    use crate::action::effect::EffectError;
     use crate::actions;
     use crate::prelude::{Pipe, Reactor, Then};
+    use crate::test_util::update_until_some;
     use crate::tests::{exit_reader, test_app};
 
     #[test]
@@ -155,6 +235,23 @@ mod tests {
         app.assert_resource_eq(Count(2));
     }
 
+    #[test]
+    fn output_panic_message() {
+        let mut app = test_app();
+        let result = Arc::new(Mutex::new(None));
+        let out = result.clone();
+        app.world.spawn(Reactor::schedule(|task| async move {
+            let result = task.will(Update, effect::tokio::try_spawn(async move {
+                panic!("failed to connect");
+            })).await;
+            out.lock().unwrap().replace(result);
+        }));
+        assert_eq!(
+            update_until_some(&mut app, &result),
+            Err::<(), _>(EffectError::Panicked("failed to connect".to_string()))
+        );
+    }
+
     #[test]
     fn cancel_tokio_task() {
         let mut app = test_app();
@@ -0,0 +1,289 @@
+use bevy::app::{App, Plugin};
+use bevy::prelude::Resource;
+use tokio::runtime::{Builder, Handle, Runtime};
+use tokio::sync::oneshot;
+
+const THREAD_NAME: &str = "flurx-tokio";
+
+/// Runs [`effect::tokio`](crate::prelude::effect::tokio) actions on a tokio runtime managed by this plugin,
+/// or on a runtime you already have.
+///
+/// Without this plugin, the actions fall back to the runtime that `async-compat` creates lazily in the background.
+/// With it, the tasks are spawned onto [`TokioRuntime::handle`] directly,
+/// and [`Reactor`](crate::prelude::Reactor) is polled in the context of that runtime,
+/// so the hidden runtime is never created.
+///
+/// The runtime is shut down when [`TokioRuntime`] is removed, unless it was given by [`FlurxTokioPlugin::from_handle`].
+///
+/// ## Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_flurx::prelude::*;
+///
+/// App::new()
+///     .add_plugins((
+///         DefaultPlugins,
+///         FlurxPlugin::default(),
+///         FlurxTokioPlugin::default().worker_threads(2),
+///     ));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FlurxTokioPlugin {
+    runtime: RuntimeConfig,
+}
+
+#[derive(Debug, Clone)]
+enum RuntimeConfig {
+    MultiThread {
+        worker_threads: Option<usize>,
+    },
+    CurrentThread,
+    Handle(Handle),
+}
+
+impl Default for RuntimeConfig {
+    #[inline]
+    fn default() -> Self {
+        Self::MultiThread {
+            worker_threads: None,
+        }
+    }
+}
+
+impl FlurxTokioPlugin {
+    /// Runs the tasks on a runtime that uses only one thread.
+    ///
+    /// The thread is spawned by this plugin and drives the runtime until it is shut down.
+    #[inline]
+    pub fn current_thread() -> Self {
+        Self {
+            runtime: RuntimeConfig::CurrentThread,
+        }
+    }
+
+    /// Runs the tasks on the runtime that `handle` refers to instead of creating one.
+    ///
+    /// The runtime is not shut down by this plugin.
+    ///
+    /// ## Examples
+    ///
+    /// ```no_run
+    /// use bevy::prelude::*;
+    /// use bevy_flurx::prelude::*;
+    ///
+    /// let runtime = tokio::runtime::Runtime::new().unwrap();
+    /// App::new()
+    ///     .add_plugins((
+    ///         DefaultPlugins,
+    ///         FlurxPlugin::default(),
+    ///         FlurxTokioPlugin::from_handle(runtime.handle().clone()),
+    ///     ))
+    ///     .run();
+    /// ```
+    #[inline]
+    pub fn from_handle(handle: Handle) -> Self {
+        Self {
+            runtime: RuntimeConfig::Handle(handle),
+        }
+    }
+
+    /// Runs the tasks on a multi-thread runtime with `worker_threads` worker threads.
+    ///
+    /// The default is the number of cpu cores.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `worker_threads` is 0.
+    #[inline]
+    pub fn worker_threads(self, worker_threads: usize) -> Self {
+        assert!(0 < worker_threads, "the runtime needs at least one worker thread");
+        Self {
+            runtime: RuntimeConfig::MultiThread {
+                worker_threads: Some(worker_threads),
+            },
+        }
+    }
+}
+
+impl Plugin for FlurxTokioPlugin {
+    fn build(&self, app: &mut App) {
+        let runtime = match &self.runtime {
+            RuntimeConfig::MultiThread { worker_threads } => {
+                let mut builder = Builder::new_multi_thread();
+                if let Some(worker_threads) = worker_threads {
+                    builder.worker_threads(*worker_threads);
+                }
+                TokioRuntime::multi_thread(build_runtime(&mut builder))
+            }
+            RuntimeConfig::CurrentThread => TokioRuntime::current_thread(build_runtime(&mut Builder::new_current_thread())),
+            RuntimeConfig::Handle(handle) => TokioRuntime {
+                handle: handle.clone(),
+                _owned: None,
+            },
+        };
+        app.insert_resource(runtime);
+    }
+}
+
+/// The tokio runtime that [`effect::tokio`](crate::prelude::effect::tokio) actions run on.
+///
+/// Inserted by [`FlurxTokioPlugin`].
+#[derive(Resource, Debug)]
+pub struct TokioRuntime {
+    handle: Handle,
+    /// Shuts down the runtime when dropped.
+    _owned: Option<OwnedRuntime>,
+}
+
+impl TokioRuntime {
+    /// Returns the handle of the runtime.
+    ///
+    /// It can be used to spawn your own tasks on the same runtime.
+    #[inline]
+    pub fn handle(&self) -> &Handle {
+        &self.handle
+    }
+
+    fn multi_thread(runtime: Runtime) -> Self {
+        Self {
+            handle: runtime.handle().clone(),
+            _owned: Some(OwnedRuntime::MultiThread(Some(runtime))),
+        }
+    }
+
+    fn current_thread(runtime: Runtime) -> Self {
+        let handle = runtime.handle().clone();
+        let (shutdown, shutdown_rx) = oneshot::channel::<()>();
+        std::thread::Builder::new()
+            .name(THREAD_NAME.to_string())
+            .spawn(move || {
+                // The spawned tasks only make progress while the runtime is blocked on.
+                let _ = runtime.block_on(shutdown_rx);
+            })
+            .expect("failed to spawn the tokio runtime thread");
+        Self {
+            handle,
+            _owned: Some(OwnedRuntime::CurrentThread(Some(shutdown))),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum OwnedRuntime {
+    MultiThread(Option<Runtime>),
+    CurrentThread(Option<oneshot::Sender<()>>),
+}
+
+impl Drop for OwnedRuntime {
+    fn drop(&mut self) {
+        // Dropping the runtime waits for its blocking tasks, which would stall the main thread.
+        match self {
+            Self::MultiThread(runtime) => {
+                if let Some(runtime) = runtime.take() {
+                    runtime.shutdown_background();
+                }
+            }
+            Self::CurrentThread(shutdown) => {
+                if let Some(shutdown) = shutdown.take() {
+                    let _ = shutdown.send(());
+                }
+            }
+        }
+    }
+}
+
+fn build_runtime(builder: &mut Builder) -> Runtime {
+    builder
+        .thread_name(THREAD_NAME)
+        .enable_all()
+        .build()
+        .expect("failed to build the tokio runtime")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, Instant};
+
+    use bevy::app::App;
+    use bevy::prelude::Update;
+    use tokio::task::JoinHandle;
+
+    use crate::action::effect;
+    use crate::action::effect::tokio::{FlurxTokioPlugin, TokioRuntime};
+    use crate::prelude::Reactor;
+    use crate::test_util::update_until_some;
+    use crate::tests::test_app;
+
+    fn thread_name() -> Option<String> {
+        std::thread::current().name().map(String::from)
+    }
+
+    fn spawn_thread_name_reactor(app: &mut App) -> Arc<Mutex<Option<Option<String>>>> {
+        let name = Arc::new(Mutex::new(None));
+        let out = name.clone();
+        app.world.spawn(Reactor::schedule(|task| async move {
+            let name = task.will(Update, effect::tokio::spawn(async move {
+                thread_name()
+            })).await;
+            out.lock().unwrap().replace(name);
+        }));
+        name
+    }
+
+    #[test]
+    fn spawn_on_owned_runtime() {
+        for plugin in [FlurxTokioPlugin::default().worker_threads(2), FlurxTokioPlugin::current_thread()] {
+            let mut app = test_app();
+            app.add_plugins(plugin);
+            let name = spawn_thread_name_reactor(&mut app);
+            assert_eq!(update_until_some(&mut app, &name), Some("flurx-tokio".to_string()));
+        }
+    }
+
+    #[test]
+    fn spawn_on_given_handle() {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("game-runtime")
+            .enable_all()
+            .build()
+            .unwrap();
+        let mut app = test_app();
+        app.add_plugins(FlurxTokioPlugin::from_handle(runtime.handle().clone()));
+        let name = spawn_thread_name_reactor(&mut app);
+        assert_eq!(update_until_some(&mut app, &name), Some("game-runtime".to_string()));
+        drop(app);
+        runtime.shutdown_background();
+    }
+
+    #[test]
+    fn reactor_runs_in_runtime_context() {
+        let mut app = test_app();
+        app.add_plugins(FlurxTokioPlugin::current_thread());
+        let handle: Arc<Mutex<Option<JoinHandle<Option<String>>>>> = Arc::new(Mutex::new(None));
+        let out = handle.clone();
+        app.world.spawn(Reactor::schedule(|_| async move {
+            out.lock().unwrap().replace(tokio::spawn(async move {
+                thread_name()
+            }));
+        }));
+        let handle = update_until_some(&mut app, &handle);
+        assert_eq!(pollster::block_on(handle).unwrap(), Some("flurx-tokio".to_string()));
+    }
+
+    #[test]
+    fn shutdown_owned_runtime_on_remove() {
+        let mut app = test_app();
+        app.add_plugins(FlurxTokioPlugin::current_thread());
+        let handle = app.world.resource::<TokioRuntime>().handle().clone();
+        let task = handle.spawn(std::future::pending::<()>());
+        app.world.remove_resource::<TokioRuntime>();
+        let start = Instant::now();
+        while !task.is_finished() {
+            assert!(start.elapsed() < Duration::from_secs(5));
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+}
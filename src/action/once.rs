@@ -2,6 +2,7 @@
 //!
 //! actions
 //!
+//! - [`once::run_parallel`](crate::prelude::once::run_parallel)
 //! - [`once::res`](crate::prelude::once::res)
 //! - [`once::non_send`](crate::prelude::once::res)
 //! - [`once::event`](crate::prelude::once::res)
@@ -12,7 +13,9 @@
 
 use bevy::prelude::{IntoSystem, System, World};
 
+use crate::action::parallel::ParallelRunner;
 use crate::action::seed::ActionSeed;
+use crate::action::system_cache::CachedSystem;
 use crate::prelude::Action;
 use crate::runner::{CancellationToken, Output, Runner};
 
@@ -53,11 +56,90 @@ pub fn run<Sys, I, Out, M>(system: Sys) -> ActionSeed<I, Out>
         OnceRunner{
             input: Some(input),
             output,
-            system: IntoSystem::into_system(system)      
+            system: CachedSystem::new(IntoSystem::into_system(system)),
         }
     })
 }
 
+/// Once run a system, and reuse the initialized system the next time this action is created from the same system.
+///
+/// This avoids initializing the system every time when the action is run repeatedly, such as in a loop.
+/// Note that the state of [`Local`](bevy::prelude::Local) and [`EventReader`](bevy::prelude::EventReader)
+/// persists between uses.
+///
+/// The initialized system is reused only under these conditions:
+///
+/// - The system does not capture any values, such as a function or a closure without captures.
+///   A closure with captures behaves the same as [`once::run`](crate::prelude::once::run).
+/// - The system is keyed by its type, so the actions created by the same closure in a loop share the system,
+///   while two closures with the same body written in different places do not.
+/// - The system is returned for reuse when the action finishes. A cancelled action drops its system.
+/// - The actions running at the same time each initialize their own system.
+///
+/// ## Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_flurx::prelude::*;
+///
+/// Reactor::schedule(|task| async move{
+///     loop{
+///         task.will(Update, once::run_cached(|mut count: Local<usize>|{
+///             *count += 1;
+///         })).await;
+///     }
+/// });
+/// ```
+#[inline(always)]
+pub fn run_cached<Sys, I, Out, M>(system: Sys) -> ActionSeed<I, Out>
+    where
+        Sys: IntoSystem<I, Out, M> + 'static,
+        I: 'static,
+        Out: 'static
+{
+    ActionSeed::new(move |input, output| {
+        OnceRunner{
+            input: Some(input),
+            output,
+            system: CachedSystem::cached::<Sys>(IntoSystem::into_system(system)),
+        }
+    })
+}
+
+/// Once run a system on another thread, together with the systems of other parallel actions.
+///
+/// The systems of the parallel actions queued in the same schedule are run right after its runners,
+/// and those whose accesses do not conflict are run in parallel on [`ComputeTaskPool`](bevy::tasks::ComputeTaskPool).
+/// Systems that are not `Send`, such as those using [`NonSend`](bevy::prelude::NonSend), are run on the main thread.
+///
+/// The output is passed in the same frame, but the other runners of the schedule have already run by then,
+/// so a following action in [`Then`](crate::prelude::Then) or [`Pipe`](crate::prelude::Pipe) starts in the next frame.
+///
+/// ## Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_flurx::prelude::*;
+///
+/// Reactor::schedule(|task| async move{
+///     let count = task.will(Update, once::run_parallel(|transforms: Query<&Transform>|{
+///         transforms.iter().filter(|transform| 0. < transform.translation.x).count()
+///     })).await;
+/// });
+/// ```
+#[inline(always)]
+pub fn run_parallel<Sys, I, Out, M>(system: Sys) -> ActionSeed<I, Out>
+    where
+        Sys: IntoSystem<I, Out, M> + 'static,
+        I: Send + 'static,
+        Out: Send + 'static
+{
+    ActionSeed::new(move |input, output| {
+        let mut input = Some(input);
+        ParallelRunner::new(IntoSystem::into_system(system), move || input.take(), output, Some)
+    })
+}
+
 /// Once run a system with input.
 ///
 /// The return value will be the system return value.
@@ -86,7 +168,7 @@ pub fn run_with<Sys, Input, Out, Marker>(input: Input, system: Sys) -> Action<In
 }
 
 struct OnceRunner<Sys, I, O> {
-    system: Sys,
+    system: CachedSystem<Sys>,
     input: Option<I>,
     output: Output<O>,
 }
@@ -98,12 +180,13 @@ impl<Sys, I, O> Runner for OnceRunner<Sys, I, O>
         O: 'static
 {
     fn run(&mut self, world: &mut World, _: &CancellationToken) -> bool {
-        self.system.initialize(world);
         let Some(input) = self.input.take() else {
             return true;
         };
-        let out = self.system.run(input, world);
-        self.system.apply_deferred(world);
+        let system = self.system.get(world);
+        let out = system.run(input, world);
+        system.apply_deferred(world);
+        self.system.release(world);
         self.output.set(out);
         true
     }
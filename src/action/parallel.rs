@@ -0,0 +1,494 @@
+use std::any::Any;
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::panic::{AssertUnwindSafe, catch_unwind, resume_unwind};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use bevy::ecs::archetype::ArchetypeComponentId;
+use bevy::ecs::query::Access;
+use bevy::ecs::schedule::ScheduleLabel;
+use bevy::ecs::world::unsafe_world_cell::UnsafeWorldCell;
+use bevy::prelude::{System, World};
+use bevy::tasks::{ComputeTaskPool, TaskPool};
+use bevy::utils::HashMap;
+use bevy::utils::intern::Interned;
+
+use crate::FlurxSettings;
+use crate::runner::{CancellationToken, fail_reactor, Output, Runner};
+
+type SharedJob = Arc<Mutex<dyn ParallelJob>>;
+
+/// The systems of the parallel actions, such as [`once::run_parallel`](crate::prelude::once::run_parallel),
+/// waiting to be run on each schedule.
+///
+/// This is a non-send resource because it holds [`CancellationToken`]s of the reactors.
+#[derive(Default)]
+pub(crate) struct ParallelJobs {
+    running_schedule: Option<Interned<dyn ScheduleLabel>>,
+    queued: HashMap<Interned<dyn ScheduleLabel>, Vec<QueuedJob>>,
+}
+
+impl ParallelJobs {
+    /// Sets the schedule whose runners are about to be run, or `None` once they have been run.
+    #[inline]
+    pub(crate) fn set_running_schedule(world: &mut World, label: Option<Interned<dyn ScheduleLabel>>) {
+        if let Some(mut jobs) = world.get_non_send_resource_mut::<ParallelJobs>() {
+            jobs.running_schedule = label;
+        }
+    }
+}
+
+struct QueuedJob {
+    job: SharedJob,
+    token: CancellationToken,
+    /// Passes the output of the system to the runner.
+    deliver: Rc<dyn Fn()>,
+}
+
+trait ParallelJob: Send {
+    fn name(&self) -> Cow<'static, str>;
+
+    fn is_cancelled(&self) -> bool;
+
+    /// Initializes the system and updates its access.
+    ///
+    /// Returns `true` if the system can run on another thread.
+    fn prepare(&mut self, world: &mut World) -> bool;
+
+    fn access(&self) -> &Access<ArchetypeComponentId>;
+
+    fn run(&mut self, world: &mut World);
+
+    /// # Safety
+    ///
+    /// The world must be accessible with [`ParallelJob::access`],
+    /// and [`ParallelJob::prepare`] must be called before.
+    unsafe fn run_unsafe(&mut self, world: UnsafeWorldCell);
+
+    fn apply_deferred(&mut self, world: &mut World);
+
+    /// Takes the payload of the panic raised by the system while running.
+    fn take_panic(&mut self) -> Option<Box<dyn Any + Send>>;
+}
+
+struct Job<S: System> {
+    system: S,
+    input: Box<dyn FnMut() -> Option<S::In> + Send>,
+    output: Option<S::Out>,
+    panic: Option<Box<dyn Any + Send>>,
+    initialized: bool,
+    queued: bool,
+    cancelled: bool,
+}
+
+impl<S> Job<S>
+    where
+        S: System,
+{
+    fn run_with(&mut self, run: impl FnOnce(&mut S, S::In) -> S::Out) {
+        self.queued = false;
+        let Some(input) = (self.input)() else {
+            return;
+        };
+        match catch_unwind(AssertUnwindSafe(|| run(&mut self.system, input))) {
+            Ok(output) => self.output = Some(output),
+            Err(payload) => self.panic = Some(payload),
+        }
+    }
+}
+
+impl<S> ParallelJob for Job<S>
+    where
+        S: System,
+        S::Out: Send
+{
+    #[inline]
+    fn name(&self) -> Cow<'static, str> {
+        self.system.name()
+    }
+
+    #[inline]
+    fn is_cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    fn prepare(&mut self, world: &mut World) -> bool {
+        if !self.initialized {
+            self.system.initialize(world);
+            self.initialized = true;
+        }
+        self.system.update_archetype_component_access(world.as_unsafe_world_cell_readonly());
+        self.system.is_send() && !self.system.is_exclusive()
+    }
+
+    #[inline]
+    fn access(&self) -> &Access<ArchetypeComponentId> {
+        self.system.archetype_component_access()
+    }
+
+    fn run(&mut self, world: &mut World) {
+        self.run_with(|system, input| system.run(input, world));
+    }
+
+    unsafe fn run_unsafe(&mut self, world: UnsafeWorldCell) {
+        // SAFETY: Upheld by the caller.
+        self.run_with(|system, input| unsafe { system.run_unsafe(input, world) });
+    }
+
+    #[inline]
+    fn apply_deferred(&mut self, world: &mut World) {
+        self.system.apply_deferred(world);
+    }
+
+    #[inline]
+    fn take_panic(&mut self) -> Option<Box<dyn Any + Send>> {
+        self.panic.take()
+    }
+}
+
+/// Locks the job.
+///
+/// A panic inside the system is caught while the job is locked,
+/// but the lock is still recovered if it has been poisoned.
+#[inline]
+fn lock<T: ?Sized>(job: &Mutex<T>) -> MutexGuard<T> {
+    job.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Runs the systems of the parallel actions queued on `label` while its runners were run,
+/// and passes their outputs to the runners.
+///
+/// The systems whose accesses do not conflict are run in parallel on [`ComputeTaskPool`],
+/// and those that are not `Send` or exclusive are run on the main thread.
+/// The commands of all systems are applied after all systems have run.
+///
+/// If a system panics, the reactor fails as with the other actions
+/// if [`FlurxPlugin::catch_panics`](crate::FlurxPlugin::catch_panics) is enabled,
+/// otherwise the panic is resumed.
+pub(crate) fn run_parallel_jobs(world: &mut World, label: Interned<dyn ScheduleLabel>) {
+    let Some(jobs) = world
+        .get_non_send_resource_mut::<ParallelJobs>()
+        .and_then(|mut jobs| jobs.queued.remove(&label)) else {
+        return;
+    };
+    let jobs = jobs
+        .into_iter()
+        .filter(|queued| {
+            !(lock(&queued.job).is_cancelled()
+                || queued.token.finished_reactor()
+                || queued.token.is_cancellation_requested()
+                || queued.token.out_of_scope(world))
+        })
+        .collect::<Vec<_>>();
+
+    let mut remaining = Vec::new();
+    let mut main_thread = Vec::new();
+    for queued in &jobs {
+        if lock(&queued.job).prepare(world) {
+            remaining.push(&queued.job);
+        } else {
+            main_thread.push(&queued.job);
+        }
+    }
+
+    while !remaining.is_empty() {
+        let mut access = Access::default();
+        let mut batch = Vec::new();
+        let mut conflicted = Vec::new();
+        for job in remaining {
+            let locked = lock(job);
+            if locked.access().is_compatible(&access) {
+                access.extend(locked.access());
+                batch.push(job);
+            } else {
+                conflicted.push(job);
+            }
+        }
+        run_batch(world, &batch);
+        remaining = conflicted;
+    }
+    for job in main_thread {
+        lock(job).run(world);
+    }
+
+    let catch_panics = world
+        .get_resource::<FlurxSettings>()
+        .is_some_and(|settings| settings.catch_panics);
+    for queued in &jobs {
+        let (name, panic) = {
+            let mut job = lock(&queued.job);
+            job.apply_deferred(world);
+            (job.name(), job.take_panic())
+        };
+        match panic {
+            Some(payload) if catch_panics => fail_reactor(world, &format!("system `{name}`"), &queued.token, payload),
+            Some(payload) => resume_unwind(payload),
+            None => (queued.deliver)(),
+        }
+    }
+}
+
+fn run_batch(world: &mut World, batch: &[&SharedJob]) {
+    if let [job] = batch {
+        lock(job).run(world);
+        return;
+    }
+    let world = world.as_unsafe_world_cell();
+    ComputeTaskPool::get_or_init(TaskPool::default).scope(|scope| {
+        for job in batch {
+            scope.spawn(async move {
+                // SAFETY: The accesses of the systems in the batch do not conflict with each other,
+                // and no other systems are running because this is called from an exclusive system.
+                unsafe { lock(job).run_unsafe(world) };
+            });
+        }
+    });
+}
+
+/// The runner of the parallel actions.
+///
+/// Instead of running the system directly, it queues the system to be run
+/// right after the runners of the schedule have run, and the output is passed in the same frame.
+pub(crate) struct ParallelRunner<S: System> {
+    job: Arc<Mutex<Job<S>>>,
+    deliver: Rc<dyn Fn()>,
+    finished: Rc<Cell<bool>>,
+}
+
+impl<S> ParallelRunner<S>
+    where
+        S: System,
+        S::Out: Send
+{
+    #[inline]
+    pub(crate) fn new<O: 'static>(
+        system: S,
+        input: impl FnMut() -> Option<S::In> + Send + 'static,
+        output: Output<O>,
+        finish: fn(S::Out) -> Option<O>,
+    ) -> Self {
+        let job = Arc::new(Mutex::new(Job {
+            system,
+            input: Box::new(input),
+            output: None,
+            panic: None,
+            initialized: false,
+            queued: false,
+            cancelled: false,
+        }));
+        let finished = Rc::new(Cell::new(false));
+        let deliver = {
+            let job = job.clone();
+            let finished = finished.clone();
+            Rc::new(move || {
+                if let Some(out) = lock(&job).output.take().and_then(finish) {
+                    output.set(out);
+                    finished.set(true);
+                }
+            })
+        };
+        Self {
+            job,
+            deliver,
+            finished,
+        }
+    }
+}
+
+impl<S> Runner for ParallelRunner<S>
+    where
+        S: System,
+        S::Out: Send,
+{
+    fn run(&mut self, world: &mut World, token: &CancellationToken) -> bool {
+        if self.finished.get() {
+            return true;
+        }
+        if lock(&self.job).queued {
+            return false;
+        }
+        let Some(mut jobs) = world.get_non_send_resource_mut::<ParallelJobs>() else {
+            return false;
+        };
+        if let Some(label) = jobs.running_schedule {
+            jobs.queued.entry(label).or_default().push(QueuedJob {
+                job: self.job.clone(),
+                token: token.clone(),
+                deliver: self.deliver.clone(),
+            });
+            lock(&self.job).queued = true;
+        }
+        false
+    }
+}
+
+impl<S: System> Drop for ParallelRunner<S> {
+    fn drop(&mut self) {
+        lock(&self.job).cancelled = true;
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::Update;
+    use bevy::ecs::system::RunSystemOnce;
+    use bevy::prelude::{Commands, default, Entity, In, Query, Res, ResMut, Resource, With};
+    use bevy_test_helper::resource::count::Count;
+    use bevy_test_helper::resource::DirectResourceControl;
+
+    use crate::action::{once, wait};
+    use crate::action::parallel::ParallelJobs;
+    use crate::FlurxPlugin;
+    use crate::prelude::{Pipe, Reactor, ReactorFailed};
+    use crate::test_util::reactor_count;
+    use crate::tests::{came_event, test_app, test_app_with};
+
+    #[derive(Resource, Default, Debug, Eq, PartialEq)]
+    struct Counts([usize; 4]);
+
+    #[derive(Resource)]
+    struct Limit(usize);
+
+    #[test]
+    fn pass_output() {
+        let mut app = test_app();
+        app.world.spawn(Reactor::schedule(|task| async move {
+            task.will(Update, once::run_parallel(|In(num): In<usize>| num * 2)
+                .with(3)
+                .pipe(once::run(|In(num): In<usize>, mut count: ResMut<Count>| {
+                    count.0 = num;
+                })),
+            ).await;
+        }));
+        for _ in 0..3 {
+            app.update();
+        }
+        app.assert_resource_eq(Count(6));
+    }
+
+    #[test]
+    fn finish_in_polled_frame() {
+        let mut app = test_app();
+        app.world.spawn(Reactor::schedule(|task| async move {
+            task.will(Update, once::run_parallel(|mut count: ResMut<Count>| {
+                count.increment();
+            })).await;
+            task.will(Update, wait::until_parallel(|mut count: ResMut<Count>| {
+                count.increment();
+                count.0 == 3
+            })).await;
+            task.will(Update, once::run(|mut count: ResMut<Count>| {
+                count.increment();
+            })).await;
+        }));
+        app.update();
+        app.assert_resource_eq(Count(1));
+        app.update();
+        app.assert_resource_eq(Count(2));
+        app.update();
+        app.assert_resource_eq(Count(3));
+        app.update();
+        app.assert_resource_eq(Count(4));
+    }
+
+    #[test]
+    fn queue_job_once_jobs_exist() {
+        let mut app = test_app();
+        app.world.remove_non_send_resource::<ParallelJobs>();
+        app.world.spawn(Reactor::schedule(|task| async move {
+            task.will(Update, once::run_parallel(|mut count: ResMut<Count>| {
+                count.increment();
+            })).await;
+        }));
+        app.update();
+        app.assert_resource_eq(Count(0));
+        app.world.init_non_send_resource::<ParallelJobs>();
+        app.update();
+        app.assert_resource_eq(Count(1));
+    }
+
+    #[test]
+    fn fail_reactor_if_system_panicked() {
+        let mut app = test_app_with(FlurxPlugin {
+            catch_panics: true,
+            ..default()
+        });
+        app.world.spawn(Reactor::schedule(|task| async move {
+            task.will(Update, wait::both(
+                once::run_parallel(|| {
+                    panic!("failed to count");
+                }),
+                once::run_parallel(|mut count: ResMut<Count>| {
+                    count.increment();
+                }),
+            )).await;
+        }));
+        app.update();
+        assert!(came_event::<ReactorFailed>(&mut app));
+        app.assert_resource_eq(Count(1));
+        app.update();
+        assert_eq!(reactor_count(&mut app), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn resume_panic_by_default() {
+        let mut app = test_app();
+        app.world.spawn(Reactor::schedule(|task| async move {
+            task.will(Update, once::run_parallel(|| {
+                panic!("failed to count");
+            })).await;
+        }));
+        app.update();
+    }
+
+    #[test]
+    fn run_systems_of_many_reactors() {
+        let mut app = test_app();
+        app.insert_resource(Limit(3));
+        app.init_resource::<Counts>();
+        for i in 0..4 {
+            app.world.spawn(Reactor::schedule(move |task| async move {
+                task.will(Update, wait::until_parallel(move |mut counts: ResMut<Counts>, limit: Res<Limit>| {
+                    counts.0[i] += 1;
+                    counts.0[i] == limit.0
+                })).await;
+                task.will(Update, once::run_parallel(|mut count: ResMut<Count>| {
+                    count.increment();
+                })).await;
+            }));
+        }
+        for _ in 0..10 {
+            app.update();
+        }
+        app.assert_resource_eq(Counts([3, 3, 3, 3]));
+        app.assert_resource_eq(Count(4));
+        assert_eq!(reactor_count(&mut app), 0);
+    }
+
+    #[test]
+    fn not_run_after_cancelled() {
+        let mut app = test_app();
+        app.world.spawn(Reactor::schedule(|task| async move {
+            task.will(Update, wait::until_parallel(|mut count: ResMut<Count>| {
+                count.increment();
+                false
+            })).await;
+        }));
+        for _ in 0..3 {
+            app.update();
+        }
+        let count = app.world.resource::<Count>().0;
+        assert!(0 < count);
+
+        app.world.run_system_once(|mut commands: Commands, reactor: Query<Entity, With<Reactor>>| {
+            commands.entity(reactor.single()).despawn();
+        });
+        for _ in 0..3 {
+            app.update();
+        }
+        app.assert_resource_eq(Count(count));
+    }
+}
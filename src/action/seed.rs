@@ -1,6 +1,8 @@
 //! Provides the trait for converting into an action. 
 
 
+use std::borrow::Cow;
+
 use crate::action::Action;
 use crate::runner::{BoxedRunner, Output, Runner};
 
@@ -68,6 +70,35 @@ impl<I, O> ActionSeed<I, O>
         Action(input, self)
     }
 
+    /// Gives this action a debug name.
+    ///
+    /// The name is shown in [`ReactorDebugInfo`](crate::prelude::ReactorDebugInfo) while the action is running.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use bevy::prelude::*;
+    /// use bevy_flurx::prelude::*;
+    ///
+    /// #[derive(Resource)]
+    /// struct DoorOpened;
+    ///
+    /// Reactor::schedule(|task| async move{
+    ///     task.will(Update, wait::until(|door: Option<Res<DoorOpened>>|{
+    ///         door.is_some()
+    ///     }).named("wait for door")).await;
+    /// });
+    /// ```
+    #[inline]
+    pub fn named(self, name: impl Into<Cow<'static, str>>) -> ActionSeed<I, O> {
+        let name = name.into();
+        ActionSeed::from(move |input, output| {
+            let mut runner = self.create_runner(input, output);
+            runner.set_name(name);
+            runner
+        })
+    }
+
     #[inline(always)]
     pub(crate) fn create_runner(self, input: I, output: Output<O>) -> BoxedRunner {
         (self.0)(input, output)
@@ -0,0 +1,181 @@
+use std::any::{Any, TypeId};
+use std::mem::size_of;
+
+use bevy::prelude::{Resource, System, World};
+use bevy::utils::HashMap;
+
+/// Holds the initialized systems released by the cached actions, such as [`once::run_cached`](crate::prelude::once::run_cached).
+#[derive(Resource, Default)]
+struct SystemCache(HashMap<TypeId, Box<dyn Any + Send + Sync>>);
+
+/// The system used by a runner.
+///
+/// If it is cached, an initialized system is taken from [`SystemCache`] on first use
+/// and returned to it on [`CachedSystem::release`], so the same system is not initialized again.
+pub(crate) struct CachedSystem<S> {
+    system: SystemSlot<S>,
+    cached: bool,
+}
+
+enum SystemSlot<S> {
+    Uninitialized(S),
+    Initialized(S),
+    Released,
+}
+
+impl<S> CachedSystem<S>
+    where
+        S: System
+{
+    /// Creates a system that is initialized on first use and never cached.
+    #[inline]
+    pub(crate) fn new(system: S) -> Self {
+        Self {
+            system: SystemSlot::Uninitialized(system),
+            cached: false,
+        }
+    }
+
+    /// Creates a system that is shared through [`SystemCache`].
+    ///
+    /// The system is keyed by its type, so it is cached only if `Sys` has no captured values,
+    /// that is, if `Sys` is zero-sized.
+    /// Otherwise, the systems created from the same closure with different captured values would be mixed up.
+    #[inline]
+    pub(crate) fn cached<Sys>(system: S) -> Self {
+        Self {
+            system: SystemSlot::Uninitialized(system),
+            cached: size_of::<Sys>() == 0,
+        }
+    }
+
+    /// Returns the initialized system.
+    pub(crate) fn get(&mut self, world: &mut World) -> &mut S {
+        self.system = match std::mem::replace(&mut self.system, SystemSlot::Released) {
+            SystemSlot::Uninitialized(mut system) => {
+                let cached = self.cached.then(|| take_cached(world)).flatten();
+                SystemSlot::Initialized(cached.unwrap_or_else(|| {
+                    system.initialize(world);
+                    system
+                }))
+            }
+            slot => slot,
+        };
+        match &mut self.system {
+            SystemSlot::Initialized(system) => system,
+            _ => panic!("system has already been released"),
+        }
+    }
+
+    /// Returns the system to [`SystemCache`] if it is cached.
+    pub(crate) fn release(&mut self, world: &mut World) {
+        match std::mem::replace(&mut self.system, SystemSlot::Released) {
+            SystemSlot::Initialized(system) if self.cached => {
+                world
+                    .get_resource_or_insert_with(SystemCache::default)
+                    .0
+                    .entry(TypeId::of::<S>())
+                    .or_insert_with(|| Box::<Vec<S>>::default())
+                    .downcast_mut::<Vec<S>>()
+                    .unwrap()
+                    .push(system);
+            }
+            slot => self.system = slot,
+        }
+    }
+}
+
+fn take_cached<S: System>(world: &mut World) -> Option<S> {
+    world
+        .get_resource_mut::<SystemCache>()?
+        .0
+        .get_mut(&TypeId::of::<S>())?
+        .downcast_mut::<Vec<S>>()?
+        .pop()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::Update;
+    use bevy::prelude::{Local, ResMut};
+    use bevy_test_helper::resource::count::Count;
+    use bevy_test_helper::resource::DirectResourceControl;
+
+    use crate::action::{once, wait};
+    use crate::prelude::Reactor;
+    use crate::tests::test_app;
+
+    #[test]
+    fn reuse_system_in_loop() {
+        let mut app = test_app();
+        app.world.spawn(Reactor::schedule(|task| async move {
+            for _ in 0..3 {
+                task.will(Update, once::run_cached(|mut local: Local<usize>, mut count: ResMut<Count>| {
+                    *local += 1;
+                    count.0 = *local;
+                })).await;
+            }
+        }));
+        for _ in 0..4 {
+            app.update();
+        }
+        app.assert_resource_eq(Count(3));
+    }
+
+    #[test]
+    fn not_reuse_system_with_captures() {
+        let mut app = test_app();
+        app.world.spawn(Reactor::schedule(|task| async move {
+            for i in 0..3 {
+                task.will(Update, once::run_cached(move |mut local: Local<usize>, mut count: ResMut<Count>| {
+                    *local += 1;
+                    count.0 += *local + i;
+                })).await;
+            }
+        }));
+        for _ in 0..4 {
+            app.update();
+        }
+        app.assert_resource_eq(Count(1 + 2 + 3));
+    }
+
+    #[test]
+    fn running_cached_systems_are_not_shared() {
+        fn count_up(mut local: Local<usize>, mut count: ResMut<Count>) -> bool {
+            *local += 1;
+            count.increment();
+            *local == 2
+        }
+
+        let mut app = test_app();
+        for _ in 0..2 {
+            app.world.spawn(Reactor::schedule(|task| async move {
+                task.will(Update, wait::until_cached(count_up)).await;
+            }));
+        }
+        app.update();
+        app.update();
+        app.assert_resource_eq(Count(4));
+        app.update();
+        app.assert_resource_eq(Count(4));
+    }
+
+    #[test]
+    fn reuse_wait_output_system() {
+        let mut app = test_app();
+        app.world.spawn(Reactor::schedule(|task| async move {
+            for _ in 0..2 {
+                task.will(Update, wait::output_cached(|mut local: Local<usize>, mut count: ResMut<Count>| {
+                    *local += 1;
+                    count.increment();
+                    (*local == 2 || *local == 4).then_some(())
+                })).await;
+            }
+        }));
+        for _ in 0..5 {
+            app.update();
+        }
+        app.assert_resource_eq(Count(4));
+    }
+}
@@ -3,8 +3,12 @@
 //! actions
 //!
 //! - [`wait::output`]
+//! - [`wait::output_cached`]
+//! - [`wait::output_parallel`]
 //! - [`wait::both`]
 //! - [`wait::until`]
+//! - [`wait::until_cached`]
+//! - [`wait::until_parallel`]
 //! - [`wait::all`](crate::prelude::wait::all())
 //! - [`wait_all!`](crate::wait_all)
 //! - [`wait::either`]
@@ -23,7 +27,9 @@ pub use _both::both;
 pub use _either::*;
 pub use all::{all, private};
 
+use crate::action::parallel::ParallelRunner;
 use crate::action::seed::ActionSeed;
+use crate::action::system_cache::CachedSystem;
 use crate::prelude::wait;
 use crate::runner::{CancellationToken, Output, Runner};
 
@@ -67,10 +73,45 @@ pub fn output<Sys, Input, Out, Marker>(system: Sys) -> ActionSeed<Input, Out>
 {
     ActionSeed::new(move |input, output| {
         WaitRunner{
-            system: IntoSystem::into_system(system),
+            system: CachedSystem::new(IntoSystem::into_system(system)),
+            input,
+            output,
+        }
+    })
+}
+
+/// Run until it returns [`Option::Some`],
+/// and reuse the initialized system the next time this action is created from the same system.
+///
+/// Please see [`once::run_cached`](crate::prelude::once::run_cached) for the details of caching.
+///
+/// ## Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_flurx::prelude::*;
+///
+/// Reactor::schedule(|task| async move{
+///     loop{
+///         task.will(Update, wait::output_cached(|mut count: Local<usize>|{
+///             *count += 1;
+///             (*count == 2).then(|| *count = 0)
+///         })).await;
+///     }
+/// });
+/// ```
+#[inline(always)]
+pub fn output_cached<Sys, Input, Out, Marker>(system: Sys) -> ActionSeed<Input, Out>
+    where
+        Sys: IntoSystem<Input, Option<Out>, Marker> + 'static,
+        Input: Clone + 'static,
+        Out: 'static,
+{
+    ActionSeed::new(move |input, output| {
+        WaitRunner{
+            system: CachedSystem::cached::<Sys>(IntoSystem::into_system(system)),
             input,
             output,
-            init: false,
         }
     })
 }
@@ -97,21 +138,118 @@ pub fn until<Input, Sys, M>(system: Sys) -> ActionSeed<Input>
         Sys: IntoSystem<Input, bool, M> + 'static,
         Input: Clone + 'static,
 {
-    wait::output(system.pipe(
-        |In(finish): In<bool>| {
-            if finish {
-                Some(())
-            } else {
-                None
-            }
-        }))
+    wait::output(system.pipe(finished))
+}
+
+/// Run until it returns true,
+/// and reuse the initialized system the next time this action is created from the same system.
+///
+/// Please see [`once::run_cached`](crate::prelude::once::run_cached) for the details of caching.
+///
+/// ## Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_flurx::prelude::*;
+///
+/// Reactor::schedule(|task| async move{
+///     loop{
+///         task.will(Update, wait::until_cached(|mut count: Local<usize>|{
+///             *count += 1;
+///             let finished = *count == 4;
+///             if finished {
+///                 *count = 0;
+///             }
+///             finished
+///         })).await;
+///     }
+/// });
+///```
+#[inline(always)]
+pub fn until_cached<Input, Sys, M>(system: Sys) -> ActionSeed<Input>
+    where
+        Sys: IntoSystem<Input, bool, M> + 'static,
+        Input: Clone + 'static,
+{
+    ActionSeed::new(move |input, output| {
+        WaitRunner{
+            system: CachedSystem::cached::<Sys>(IntoSystem::into_system(system.pipe(finished))),
+            input,
+            output,
+        }
+    })
+}
+
+/// Run on another thread until it returns [`Option::Some`],
+/// together with the systems of other parallel actions.
+///
+/// Please see [`once::run_parallel`](crate::prelude::once::run_parallel) for the details of parallel running.
+///
+/// ## Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_flurx::prelude::*;
+///
+/// Reactor::schedule(|task| async move{
+///     let entity = task.will(Update, wait::output_parallel(|transforms: Query<(Entity, &Transform)>|{
+///         transforms
+///             .iter()
+///             .find_map(|(entity, transform)| (10. < transform.translation.x).then_some(entity))
+///     })).await;
+/// });
+/// ```
+#[inline(always)]
+pub fn output_parallel<Sys, Input, Out, Marker>(system: Sys) -> ActionSeed<Input, Out>
+    where
+        Sys: IntoSystem<Input, Option<Out>, Marker> + 'static,
+        Input: Clone + Send + 'static,
+        Out: Send + 'static,
+{
+    ActionSeed::new(move |input: Input, output| {
+        ParallelRunner::new(IntoSystem::into_system(system), move || Some(input.clone()), output, |out| out)
+    })
+}
+
+/// Run on another thread until it returns true,
+/// together with the systems of other parallel actions.
+///
+/// Please see [`once::run_parallel`](crate::prelude::once::run_parallel) for the details of parallel running.
+///
+/// ## Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_flurx::prelude::*;
+///
+/// Reactor::schedule(|task| async move{
+///     task.will(Update, wait::until_parallel(|transforms: Query<&Transform>|{
+///         transforms.iter().all(|transform| 10. < transform.translation.x)
+///     })).await;
+/// });
+///```
+#[inline(always)]
+pub fn until_parallel<Input, Sys, M>(system: Sys) -> ActionSeed<Input>
+    where
+        Sys: IntoSystem<Input, bool, M> + 'static,
+        Input: Clone + Send + 'static,
+{
+    wait::output_parallel(system.pipe(finished))
+}
+
+#[inline]
+fn finished(In(finish): In<bool>) -> Option<()> {
+    if finish {
+        Some(())
+    } else {
+        None
+    }
 }
 
 struct WaitRunner<Sys, I, O> {
-    system: Sys,
+    system: CachedSystem<Sys>,
     input: I,
     output: Output<O>,
-    init: bool,
 }
 
 impl<Sys, In, Out> Runner for WaitRunner<Sys, In, Out>
@@ -121,14 +259,11 @@ impl<Sys, In, Out> Runner for WaitRunner<Sys, In, Out>
         Out: 'static
 {
     fn run(&mut self, world: &mut World, _: &CancellationToken) -> bool {
-        if !self.init {
-            self.system.initialize(world);
-            self.init = true;
-        }
-
-        let out = self.system.run(self.input.clone(), world);
-        self.system.apply_deferred(world);
+        let system = self.system.get(world);
+        let out = system.run(self.input.clone(), world);
+        system.apply_deferred(world);
         if let Some(o) = out {
+            self.system.release(world);
             self.output.set(o);
             true
         } else {
@@ -1,9 +1,11 @@
 //! [`wait::state`] creates a task related to waiting to state update.
 //!
 //! - [`wait::state::becomes`]
+//! - [`wait::state::exits`]
+//! - [`wait::state::changed`]
 
 
-use bevy::prelude::{In, Res, State, States};
+use bevy::prelude::{In, Local, Res, State, States};
 
 use crate::action::wait;
 use crate::prelude::ActionSeed;
@@ -37,11 +39,73 @@ pub fn becomes<S>() -> ActionSeed<S>
     })
 }
 
+/// Waits until the state is no longer the specified.
+///
+/// ## Examples
+///
+/// ```no_run
+/// use bevy::prelude::{States, World, Update};
+/// use bevy_flurx::prelude::*;
+///
+/// #[derive(States, Eq, PartialEq, Copy, Clone, Hash, Default, Debug)]
+/// enum Status{
+///     #[default]
+///     First,
+///     Second
+/// }
+///
+/// Reactor::schedule(|task| async move {
+///     task.will(Update, wait::state::exits().with(Status::First)).await;
+/// });
+/// ```
+#[inline(always)]
+pub fn exits<S>() -> ActionSeed<S>
+    where S: States + 'static
+{
+    wait::until(move |In(current): In<S>,
+                      state_now: Res<State<S>>| {
+        state_now.as_ref() != &current
+    })
+}
+
+/// Waits until the state changes from the one at the time this action started,
+/// and returns the new state.
+///
+/// ## Examples
+///
+/// ```no_run
+/// use bevy::prelude::{States, World, Update};
+/// use bevy_flurx::prelude::*;
+///
+/// #[derive(States, Eq, PartialEq, Copy, Clone, Hash, Default, Debug)]
+/// enum Status{
+///     #[default]
+///     First,
+///     Second
+/// }
+///
+/// Reactor::schedule(|task| async move {
+///     let next: Status = task.will(Update, wait::state::changed()).await;
+/// });
+/// ```
+#[inline(always)]
+pub fn changed<S>() -> ActionSeed<(), S>
+    where S: States + 'static
+{
+    wait::output(|mut started: Local<Option<S>>,
+                  state_now: Res<State<S>>| {
+        let started = started.get_or_insert_with(|| state_now.get().clone());
+        (state_now.get() != started).then(|| state_now.get().clone())
+    })
+}
+
 
 #[cfg(test)]
 mod tests {
     use bevy::app::{AppExit, First, Startup, Update};
-    use bevy::prelude::{Commands, States};
+    use bevy::prelude::{Commands, In, NextState, ResMut, States};
+    use bevy_test_helper::resource::count::Count;
+    use bevy_test_helper::resource::DirectResourceControl;
 
     use crate::prelude::*;
     use crate::tests::test_app;
@@ -70,4 +134,47 @@ mod tests {
         app.update();
         assert!(app.world.get_non_send_resource::<AppExit>().is_some());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn wait_until_state_exits_phase1() {
+        let mut app = test_app();
+        app.init_state::<TestState>()
+            .add_systems(Startup, |mut commands: Commands| {
+                commands.spawn(Reactor::schedule(|task| async move {
+                    task.will(First, wait::state::exits().with(TestState::Phase1)).await;
+                    task.will(Update, once::non_send::init::<AppExit>()).await;
+                }));
+            });
+        app.update();
+        app.update();
+        assert!(app.world.get_non_send_resource::<AppExit>().is_none());
+        app.insert_state(TestState::Phase2);
+        app.update();
+        app.update();
+        assert!(app.world.get_non_send_resource::<AppExit>().is_some());
+    }
+
+    #[test]
+    fn wait_until_state_changed() {
+        let mut app = test_app();
+        app.insert_state(TestState::Phase2)
+            .add_systems(Startup, |mut commands: Commands| {
+                commands.spawn(Reactor::schedule(|task| async move {
+                    task.will(Update, wait::state::changed::<TestState>()
+                        .pipe(once::run(|In(state): In<TestState>, mut count: ResMut<Count>| {
+                            if state == TestState::Phase1 {
+                                count.increment();
+                            }
+                        })),
+                    ).await;
+                }));
+            });
+        app.update();
+        app.update();
+        app.assert_resource_eq(Count(0));
+        app.world.resource_mut::<NextState<TestState>>().set(TestState::Phase1);
+        app.update();
+        app.update();
+        app.assert_resource_eq(Count(1));
+    }
+}
@@ -0,0 +1,173 @@
+//! Provides [`FlurxDiagnosticsPlugin`] to measure reactors and runners with bevy's diagnostics.
+
+use std::time::Duration;
+
+use bevy::app::{App, Plugin};
+use bevy::diagnostic::{Diagnostic, DiagnosticMeasurement, DiagnosticPath, DiagnosticsStore, RegisterDiagnostic};
+use bevy::ecs::schedule::ScheduleLabel;
+use bevy::prelude::{IntoSystemConfigs, Query, ResMut, Resource, With};
+use bevy::utils::{HashMap, Instant};
+use bevy::utils::intern::Interned;
+
+use crate::{run_reactors, RunReactor};
+use crate::prelude::Reactor;
+
+/// Registers the diagnostics of [`Reactor`]s and runners into [`DiagnosticsStore`].
+///
+/// The measurements are taken at the end of each frame.
+///
+/// - [`FlurxDiagnosticsPlugin::REACTORS`]: the number of live [`Reactor`]s.
+/// - [`FlurxDiagnosticsPlugin::RUNNERS`]: the number of running runners.
+/// - `flurx/runners/<schedule label>`: the number of running runners per schedule label.
+/// - [`FlurxDiagnosticsPlugin::RUNNERS_STARTED`]: the number of runners started in the frame.
+/// - [`FlurxDiagnosticsPlugin::RUNNERS_FINISHED`]: the number of runners finished or cancelled in the frame.
+/// - [`FlurxDiagnosticsPlugin::RUN_REACTORS_TIME`]: the time spent running [`Reactor`]s in milliseconds.
+/// - [`FlurxDiagnosticsPlugin::RUN_RUNNERS_TIME`]: the time spent running runners in milliseconds.
+///
+/// ## Examples
+///
+/// ```no_run
+/// use bevy::diagnostic::LogDiagnosticsPlugin;
+/// use bevy::prelude::*;
+/// use bevy_flurx::prelude::*;
+///
+/// App::new()
+///     .add_plugins((
+///         DefaultPlugins,
+///         FlurxPlugin::default(),
+///         FlurxDiagnosticsPlugin,
+///         LogDiagnosticsPlugin::default(),
+///     ));
+/// ```
+#[derive(Default)]
+pub struct FlurxDiagnosticsPlugin;
+
+impl FlurxDiagnosticsPlugin {
+    /// The number of live [`Reactor`]s.
+    pub const REACTORS: DiagnosticPath = DiagnosticPath::const_new("flurx/reactors");
+
+    /// The number of running runners over all schedule labels.
+    pub const RUNNERS: DiagnosticPath = DiagnosticPath::const_new("flurx/runners");
+
+    /// The number of runners started in the frame.
+    pub const RUNNERS_STARTED: DiagnosticPath = DiagnosticPath::const_new("flurx/runners_started");
+
+    /// The number of runners finished or cancelled in the frame.
+    pub const RUNNERS_FINISHED: DiagnosticPath = DiagnosticPath::const_new("flurx/runners_finished");
+
+    /// The time spent running [`Reactor`]s in the frame.
+    pub const RUN_REACTORS_TIME: DiagnosticPath = DiagnosticPath::const_new("flurx/run_reactors_time");
+
+    /// The time spent running runners in the frame.
+    pub const RUN_RUNNERS_TIME: DiagnosticPath = DiagnosticPath::const_new("flurx/run_runners_time");
+
+    /// Returns the path of the diagnostic showing the number of running runners on `label`.
+    pub fn runners_on(label: impl ScheduleLabel) -> DiagnosticPath {
+        runners_path(label.intern())
+    }
+}
+
+impl Plugin for FlurxDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<FlurxStats>()
+            .register_diagnostic(Diagnostic::new(Self::REACTORS))
+            .register_diagnostic(Diagnostic::new(Self::RUNNERS))
+            .register_diagnostic(Diagnostic::new(Self::RUNNERS_STARTED))
+            .register_diagnostic(Diagnostic::new(Self::RUNNERS_FINISHED))
+            .register_diagnostic(Diagnostic::new(Self::RUN_REACTORS_TIME).with_suffix("ms"))
+            .register_diagnostic(Diagnostic::new(Self::RUN_RUNNERS_TIME).with_suffix("ms"))
+            .add_systems(RunReactor, measure.after(run_reactors));
+    }
+}
+
+/// The statistics collected while [`FlurxDiagnosticsPlugin`] is added.
+#[derive(Resource, Default)]
+pub(crate) struct FlurxStats {
+    pub runners_started: usize,
+    pub runners_finished: usize,
+    pub runners: HashMap<Interned<dyn ScheduleLabel>, usize>,
+    pub run_reactors_time: Duration,
+    pub run_runners_time: Duration,
+}
+
+fn runners_path(label: Interned<dyn ScheduleLabel>) -> DiagnosticPath {
+    DiagnosticPath::new(format!("flurx/runners/{}", format!("{label:?}").replace('/', "_")))
+}
+
+fn add_measurement(store: &mut DiagnosticsStore, path: &DiagnosticPath, time: Instant, value: f64) {
+    if let Some(diagnostic) = store.get_mut(path).filter(|diagnostic| diagnostic.is_enabled) {
+        diagnostic.add_measurement(DiagnosticMeasurement { time, value });
+    }
+}
+
+fn measure(
+    mut store: ResMut<DiagnosticsStore>,
+    mut stats: ResMut<FlurxStats>,
+    reactors: Query<(), With<Reactor>>,
+) {
+    let now = Instant::now();
+    let store = store.as_mut();
+    add_measurement(store, &FlurxDiagnosticsPlugin::REACTORS, now, reactors.iter().len() as f64);
+    add_measurement(store, &FlurxDiagnosticsPlugin::RUNNERS, now, stats.runners.values().sum::<usize>() as f64);
+    add_measurement(store, &FlurxDiagnosticsPlugin::RUNNERS_STARTED, now, stats.runners_started as f64);
+    add_measurement(store, &FlurxDiagnosticsPlugin::RUNNERS_FINISHED, now, stats.runners_finished as f64);
+    add_measurement(store, &FlurxDiagnosticsPlugin::RUN_REACTORS_TIME, now, stats.run_reactors_time.as_secs_f64() * 1000.);
+    add_measurement(store, &FlurxDiagnosticsPlugin::RUN_RUNNERS_TIME, now, stats.run_runners_time.as_secs_f64() * 1000.);
+
+    for (label, runners) in stats.runners.iter() {
+        let path = runners_path(*label);
+        if store.get(&path).is_none() {
+            store.add(Diagnostic::new(path.clone()));
+        }
+        add_measurement(store, &path, now, *runners as f64);
+    }
+
+    stats.runners_started = 0;
+    stats.runners_finished = 0;
+    stats.run_reactors_time = Duration::ZERO;
+    stats.run_runners_time = Duration::ZERO;
+}
+
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::{App, First, Update};
+    use bevy::diagnostic::{DiagnosticPath, DiagnosticsStore};
+
+    use crate::action::{delay, wait};
+    use crate::diagnostics::FlurxDiagnosticsPlugin;
+    use crate::prelude::Reactor;
+    use crate::tests::test_app;
+
+    fn value(app: &App, path: &DiagnosticPath) -> Option<f64> {
+        app.world.resource::<DiagnosticsStore>().get(path).and_then(|diagnostic| diagnostic.value())
+    }
+
+    #[test]
+    fn measure_reactors_and_runners() {
+        let mut app = test_app();
+        app.add_plugins(FlurxDiagnosticsPlugin);
+        app.world.spawn(Reactor::schedule(|task| async move {
+            task.will(Update, wait::until(|| false)).await;
+        }));
+        app.world.spawn(Reactor::schedule(|task| async move {
+            task.will(First, delay::frames().with(1)).await;
+        }));
+        app.update();
+        assert_eq!(value(&app, &FlurxDiagnosticsPlugin::REACTORS), Some(2.));
+        assert_eq!(value(&app, &FlurxDiagnosticsPlugin::RUNNERS_STARTED), Some(2.));
+        assert_eq!(value(&app, &FlurxDiagnosticsPlugin::RUNNERS_FINISHED), Some(0.));
+        assert_eq!(value(&app, &FlurxDiagnosticsPlugin::RUNNERS), Some(2.));
+        assert_eq!(value(&app, &FlurxDiagnosticsPlugin::runners_on(Update)), Some(1.));
+        assert_eq!(value(&app, &FlurxDiagnosticsPlugin::runners_on(First)), Some(1.));
+
+        app.update();
+        assert_eq!(value(&app, &FlurxDiagnosticsPlugin::REACTORS), Some(1.));
+        assert_eq!(value(&app, &FlurxDiagnosticsPlugin::RUNNERS_STARTED), Some(0.));
+        assert_eq!(value(&app, &FlurxDiagnosticsPlugin::RUNNERS_FINISHED), Some(1.));
+        assert_eq!(value(&app, &FlurxDiagnosticsPlugin::RUNNERS), Some(1.));
+        assert_eq!(value(&app, &FlurxDiagnosticsPlugin::runners_on(First)), Some(0.));
+        assert!(value(&app, &FlurxDiagnosticsPlugin::RUN_RUNNERS_TIME).is_some());
+    }
+}
@@ -2,14 +2,41 @@
 
 use std::future::Future;
 
-use bevy::prelude::{Commands, EntityWorldMut, World};
+use bevy::ecs::system::EntityCommands;
+use bevy::prelude::{Commands, Entity, EntityWorldMut, World};
+
+pub use entity::EntityReactorExtension;
+pub use trigger::{ReentrancyPolicy, TriggerReactorExtension};
 
 use crate::prelude::Reactor;
 use crate::task::ReactiveTask;
 use crate::world_ptr::WorldPtr;
 
+mod entity;
+mod trigger;
+
+/// Functions that create the processing flow of [`Reactor`] from a task and an input.
+///
+/// This trait is implemented for all `Fn(ReactiveTask, In) -> impl Future` that can be shared between threads,
+/// and is used by the app extensions that spawn reactors repeatedly.
+pub trait ReactorFactory<In>: Fn(ReactiveTask, In) -> <Self as ReactorFactory<In>>::Future + Send + Sync + 'static {
+    /// The processing flow created by this factory.
+    type Future: Future + 'static;
+}
+
+impl<In, F, Fut> ReactorFactory<In> for F
+    where
+        F: Fn(ReactiveTask, In) -> Fut + Send + Sync + 'static,
+        Fut: Future + 'static
+{
+    type Future = Fut;
+}
+
 /// Provides a way to create and initialize [`Reactor`] in the ecs systems.
 ///
+/// Unlike spawning [`Reactor`] directly, the reactor is started right away regardless of [`StartMode`](crate::prelude::StartMode),
+/// or when the commands are applied in the case of [`Commands`].
+///
 /// This trait is implemented in [`World`] and [`Commands`].
 ///
 /// [`World`]: bevy::prelude::World
@@ -25,10 +52,13 @@ impl<'w, Fun, Fut> ScheduleReactor<'w, Fun, Fut, EntityWorldMut<'w>> for &'w mut
         Fut: Future + 'static
 {
     fn spawn_initialized_reactor(self, f: Fun) -> EntityWorldMut<'w> {
+        let entity = self.spawn_empty().id();
         let mut reactor = Reactor::schedule(f);
-        reactor.run_sync(WorldPtr::new(self));
-        reactor.initialized = true;
-        self.spawn(reactor)
+        reactor.set_entity(entity);
+        reactor.start(WorldPtr::new(self));
+        let mut entity_mut = self.entity_mut(entity);
+        entity_mut.insert(reactor);
+        entity_mut
     }
 }
 
@@ -45,17 +75,85 @@ impl<'w, 'b, F, Fut> ScheduleReactor<'w, F, Fut, ()> for &mut Commands<'w, 'b>
     }
 }
 
+/// Provides a way to run [`Reactor`] whose lifetime is tied to an existing entity.
+///
+/// The reactor is spawned on its own entity, so the owner entity is left as it is
+/// after the processing flow has completed.
+/// On the other hand, despawning the owner entity cancels the processing flow.
+///
+/// This trait is implemented in [`EntityWorldMut`] and [`EntityCommands`].
+///
+/// [`EntityWorldMut`]: bevy::prelude::EntityWorldMut
+/// [`EntityCommands`]: bevy::ecs::system::EntityCommands
+pub trait AttachReactor {
+    /// Run [`Reactor`] bound to this entity.
+    ///
+    /// The entity is passed to `f` together with the task.
+    ///
+    /// ## Examples
+    ///
+    /// ```no_run
+    /// use bevy::prelude::*;
+    /// use bevy_flurx::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Enemy;
+    ///
+    /// fn spawn_enemy(mut commands: Commands) {
+    ///     commands
+    ///         .spawn(Enemy)
+    ///         .attach_reactor(|task, entity| async move {
+    ///             task.will(Update, wait::until(move |enemies: Query<&Enemy>| {
+    ///                 enemies.get(entity).is_err()
+    ///             })).await;
+    ///         });
+    /// }
+    /// ```
+    fn attach_reactor<F>(&mut self, f: impl FnOnce(ReactiveTask, Entity) -> F + 'static) -> &mut Self
+        where
+            F: Future;
+}
+
+impl AttachReactor for EntityWorldMut<'_> {
+    fn attach_reactor<F>(&mut self, f: impl FnOnce(ReactiveTask, Entity) -> F + 'static) -> &mut Self
+        where
+            F: Future
+    {
+        let owner = self.id();
+        self.world_scope(|world| {
+            world.spawn(Reactor::attach(owner, f));
+        });
+        self
+    }
+}
+
+impl AttachReactor for EntityCommands<'_> {
+    fn attach_reactor<F>(&mut self, f: impl FnOnce(ReactiveTask, Entity) -> F + 'static) -> &mut Self
+        where
+            F: Future
+    {
+        let owner = self.id();
+        self.commands().spawn(Reactor::attach(owner, f));
+        self
+    }
+}
+
 
 #[cfg(test)]
 mod tests {
     use bevy::ecs::system::RunSystemOnce;
-    use bevy::prelude::{Commands, Update, World};
+    use bevy::prelude::{Commands, Component, DespawnRecursiveExt, Entity, In, Query, ResMut, Update, With, World};
     use bevy_test_helper::resource::count::Count;
     use bevy_test_helper::resource::DirectResourceControl;
 
-    use crate::extension::ScheduleReactor;
+    use crate::action::{once, wait};
+    use crate::extension::{AttachReactor, ScheduleReactor};
+    use crate::prelude::Reactor;
     use crate::tests::{increment_count, test_app};
 
+    #[derive(Component)]
+    struct Owner;
+
      #[test]
     fn world_extension() {
         let mut app = test_app();
@@ -81,4 +179,53 @@ mod tests {
         app.update();
         app.assert_resource_eq(Count(1));
     }
+
+    #[test]
+    fn owner_survives_after_attached_reactor_finished() {
+        let mut app = test_app();
+        app.world.spawn(Owner).attach_reactor(|task, _| async move {
+            task.will(Update, increment_count()).await;
+        });
+        app.update();
+        app.update();
+        app.assert_resource_eq(Count(1));
+        assert!(app.world.query::<&Reactor>().get_single(&app.world).is_err());
+        assert!(app.world.query_filtered::<Entity, With<Owner>>().get_single(&app.world).is_ok());
+    }
+
+    #[test]
+    fn cancel_attached_reactor_if_owner_despawned() {
+        let mut app = test_app();
+        app.world.run_system_once(|mut commands: Commands| {
+            commands.spawn(Owner).attach_reactor(|task, _| async move {
+                task.will(Update, wait::until(|mut count: ResMut<Count>| {
+                    count.increment();
+                    false
+                })).await;
+            });
+        });
+        app.update();
+        app.assert_resource_eq(Count(1));
+
+        app.world.run_system_once(|mut commands: Commands, owner: Query<Entity, With<Owner>>| {
+            commands.entity(owner.single()).despawn_recursive();
+        });
+        for _ in 0..10 {
+            app.update();
+        }
+        app.assert_resource_eq(Count(1));
+        assert!(app.world.query::<&Reactor>().get_single(&app.world).is_err());
+    }
+
+    #[test]
+    fn pass_owner_entity() {
+        let mut app = test_app();
+        let owner = app.world.spawn(Owner).attach_reactor(|task, entity| async move {
+            task.will(Update, once::run(|In(entity): In<Entity>, mut count: ResMut<Count>| {
+                count.0 = entity.index() as usize;
+            }).with(entity)).await;
+        }).id();
+        app.update();
+        app.assert_resource_eq(Count(owner.index() as usize));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,180 @@
+use std::sync::Arc;
+
+use bevy::app::{App, PostUpdate};
+use bevy::prelude::{Added, Commands, Component, DespawnRecursiveExt, Entity, Local, Query, RemovedComponents, With};
+use bevy::utils::HashMap;
+
+use crate::extension::ReactorFactory;
+use crate::prelude::Reactor;
+
+/// Allows [`Reactor`] to be run for each entity that has a component.
+pub trait EntityReactorExtension {
+    /// Spawns [`Reactor`] for every entity to which `C` is added.
+    ///
+    /// Each reactor is bound to its entity the same way as [`AttachReactor`](crate::prelude::AttachReactor),
+    /// and is cancelled when `C` is removed from the entity or the entity is despawned.
+    /// If `C` is inserted again, a new reactor is spawned.
+    ///
+    /// Only the additions and removals of `C` are checked, in [`PostUpdate`](bevy::app::PostUpdate).
+    /// So a reactor that has finished is not spawned again while the entity keeps `C`.
+    ///
+    /// `C` must be a single component; query filters such as `With<A>` or `(With<A>, Without<B>)` are not supported.
+    ///
+    /// ## Examples
+    ///
+    /// ```no_run
+    /// use bevy::prelude::*;
+    /// use bevy_flurx::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Enemy;
+    ///
+    /// App::new()
+    ///     .add_plugins(FlurxPlugin::default())
+    ///     .add_reactor_for::<Enemy>(|task, entity| async move {
+    ///         loop {
+    ///             task.will(Update, delay::frames().with(60)).await;
+    ///             task.will(Update, once::run(move || {
+    ///                 info!("{entity:?} is alive");
+    ///             })).await;
+    ///         }
+    ///     });
+    /// ```
+    fn add_reactor_for<C>(&mut self, f: impl ReactorFactory<Entity>) -> &mut Self
+        where
+            C: Component;
+}
+
+impl EntityReactorExtension for App {
+    fn add_reactor_for<C>(&mut self, f: impl ReactorFactory<Entity>) -> &mut Self
+        where
+            C: Component
+    {
+        let f = Arc::new(f);
+        self.add_systems(PostUpdate, move |mut commands: Commands,
+                                           mut reactors: Local<HashMap<Entity, Entity>>,
+                                           running: Query<(), With<Reactor>>,
+                                           mut removed: RemovedComponents<C>,
+                                           added: Query<Entity, Added<C>>| {
+            // Removals are handled first, so that a component removed and inserted again in the same frame restarts the reactor.
+            for owner in removed.read() {
+                if let Some(reactor) = reactors.remove(&owner).and_then(|reactor| commands.get_entity(reactor)) {
+                    reactor.despawn_recursive();
+                }
+            }
+            // Forgets the reactors that have finished on their own.
+            reactors.retain(|_, reactor| running.contains(*reactor));
+            for owner in added.iter() {
+                let f = Arc::clone(&f);
+                let reactor = commands.spawn(Reactor::attach(owner, move |task, owner| f(task, owner))).id();
+                reactors.insert(owner, reactor);
+            }
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::{Component, DespawnRecursiveExt, Entity, ResMut, Update};
+    use bevy_test_helper::resource::count::Count;
+    use bevy_test_helper::resource::DirectResourceControl;
+
+    use crate::action::wait;
+    use crate::extension::EntityReactorExtension;
+    use crate::test_util::reactor_count;
+    use crate::tests::{increment_count, test_app};
+
+    #[derive(Component)]
+    struct Enemy;
+
+    fn app_with_enemy_reactors() -> bevy::app::App {
+        let mut app = test_app();
+        app.add_reactor_for::<Enemy>(|task, _: Entity| async move {
+            task.will(Update, wait::until(|mut count: ResMut<Count>| {
+                count.increment();
+                false
+            })).await;
+        });
+        app
+    }
+
+    #[test]
+    fn spawn_reactor_for_each_entity() {
+        let mut app = app_with_enemy_reactors();
+        app.world.spawn(Enemy);
+        app.world.spawn(Enemy);
+        app.update();
+        assert_eq!(reactor_count(&mut app), 2);
+        app.update();
+        app.assert_resource_eq(Count(2));
+    }
+
+    #[test]
+    fn cancel_reactor_if_component_removed() {
+        let mut app = app_with_enemy_reactors();
+        let enemy = app.world.spawn(Enemy).id();
+        app.update();
+        app.update();
+        app.assert_resource_eq(Count(1));
+
+        // The removal is detected in `PostUpdate`, so the runner in `Update` runs once more in this frame.
+        app.world.entity_mut(enemy).remove::<Enemy>();
+        app.update();
+        assert_eq!(reactor_count(&mut app), 0);
+        for _ in 0..5 {
+            app.update();
+        }
+        app.assert_resource_eq(Count(2));
+    }
+
+    #[test]
+    fn cancel_reactor_if_entity_despawned() {
+        let mut app = app_with_enemy_reactors();
+        let enemy = app.world.spawn(Enemy).id();
+        app.update();
+        app.update();
+        app.assert_resource_eq(Count(1));
+
+        app.world.entity_mut(enemy).despawn_recursive();
+        for _ in 0..5 {
+            app.update();
+        }
+        app.assert_resource_eq(Count(1));
+        assert_eq!(reactor_count(&mut app), 0);
+    }
+
+    #[test]
+    fn respawn_reactor_if_component_inserted_again() {
+        let mut app = app_with_enemy_reactors();
+        let enemy = app.world.spawn(Enemy).id();
+        app.update();
+        app.world.entity_mut(enemy).remove::<Enemy>();
+        app.update();
+        assert_eq!(reactor_count(&mut app), 0);
+
+        app.world.entity_mut(enemy).insert(Enemy);
+        app.update();
+        assert_eq!(reactor_count(&mut app), 1);
+    }
+
+    #[test]
+    fn not_respawn_finished_reactor_while_component_exists() {
+        let mut app = test_app();
+        app.add_reactor_for::<Enemy>(|task, _: Entity| async move {
+            task.will(Update, increment_count()).await;
+        });
+        let enemy = app.world.spawn(Enemy).id();
+        for _ in 0..5 {
+            app.update();
+        }
+        app.assert_resource_eq(Count(1));
+        assert_eq!(reactor_count(&mut app), 0);
+
+        // Inserting the component the entity already has does not count as an addition.
+        app.world.entity_mut(enemy).insert(Enemy);
+        app.update();
+        app.update();
+        app.assert_resource_eq(Count(1));
+    }
+}
@@ -0,0 +1,397 @@
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::Arc;
+
+use bevy::app::{App, PostUpdate};
+use bevy::prelude::{Commands, Condition, DespawnRecursiveExt, Entity, Event, EventReader, EventWriter, In, OnEnter, Query, States, With};
+
+use crate::extension::ReactorFactory;
+use crate::prelude::Reactor;
+use crate::task::ReactiveTask;
+
+/// Specifies what to do if a trigger fires while [`Reactor`] spawned by a previous trigger is still running.
+///
+/// Please see [`TriggerReactorExtension`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ReentrancyPolicy {
+    /// Spawns the new reactor after all previously spawned reactors have finished.
+    Queue,
+
+    /// Ignores the trigger.
+    Ignore,
+
+    /// Cancels the running reactors and spawns the new one.
+    Restart,
+
+    /// Spawns the new reactor alongside the running ones.
+    Parallel,
+}
+
+/// Allows [`Reactor`] to be spawned from a factory whenever a trigger fires.
+///
+/// The spawned reactors are tracked per registration,
+/// and [`ReentrancyPolicy`] decides what happens if the trigger fires again while they are running.
+///
+/// The triggers are processed in [`PostUpdate`](bevy::app::PostUpdate).
+pub trait TriggerReactorExtension {
+    /// Spawns [`Reactor`] every time the event `E` is sent.
+    ///
+    /// The event is passed to the factory.
+    ///
+    /// ## Examples
+    ///
+    /// ```no_run
+    /// use bevy::prelude::*;
+    /// use bevy_flurx::prelude::*;
+    ///
+    /// #[derive(Event, Clone)]
+    /// struct Interact(Entity);
+    ///
+    /// App::new()
+    ///     .add_plugins(FlurxPlugin::default())
+    ///     .add_event::<Interact>()
+    ///     .add_reactor_on_event::<Interact>(ReentrancyPolicy::Ignore, |task, Interact(npc)| async move {
+    ///         task.will(Update, once::run(move || {
+    ///             info!("talk to {npc:?}");
+    ///         })).await;
+    ///     });
+    /// ```
+    fn add_reactor_on_event<E>(&mut self, policy: ReentrancyPolicy, f: impl ReactorFactory<E>) -> &mut Self
+        where
+            E: Event + Clone;
+
+    /// Spawns [`Reactor`] every time the state `S` becomes `state`.
+    ///
+    /// ## Examples
+    ///
+    /// ```no_run
+    /// use bevy::prelude::*;
+    /// use bevy_flurx::prelude::*;
+    ///
+    /// #[derive(States, Eq, PartialEq, Copy, Clone, Hash, Default, Debug)]
+    /// enum GameState {
+    ///     #[default]
+    ///     Menu,
+    ///     Playing,
+    /// }
+    ///
+    /// App::new()
+    ///     .add_plugins(FlurxPlugin::default())
+    ///     .init_state::<GameState>()
+    ///     .add_reactor_on_enter(GameState::Playing, ReentrancyPolicy::Restart, |task| async move {
+    ///         task.will(Update, once::run(|| {
+    ///             info!("start");
+    ///         })).await;
+    ///     });
+    /// ```
+    fn add_reactor_on_enter<S, Fut>(
+        &mut self,
+        state: S,
+        policy: ReentrancyPolicy,
+        f: impl Fn(ReactiveTask) -> Fut + Send + Sync + 'static,
+    ) -> &mut Self
+        where
+            S: States,
+            Fut: Future + 'static;
+
+    /// Spawns [`Reactor`] in every frame the `condition` returns `true`.
+    ///
+    /// ## Examples
+    ///
+    /// ```no_run
+    /// use bevy::prelude::*;
+    /// use bevy_flurx::prelude::*;
+    ///
+    /// #[derive(Resource)]
+    /// struct Boss;
+    ///
+    /// App::new()
+    ///     .add_plugins(FlurxPlugin::default())
+    ///     .add_reactor_when(resource_added::<Boss>, ReentrancyPolicy::Ignore, |task| async move {
+    ///         task.will(Update, once::run(|| {
+    ///             info!("the boss has appeared");
+    ///         })).await;
+    ///     });
+    /// ```
+    fn add_reactor_when<M, Fut>(
+        &mut self,
+        condition: impl Condition<M>,
+        policy: ReentrancyPolicy,
+        f: impl Fn(ReactiveTask) -> Fut + Send + Sync + 'static,
+    ) -> &mut Self
+        where
+            Fut: Future + 'static;
+}
+
+impl TriggerReactorExtension for App {
+    fn add_reactor_on_event<E>(&mut self, policy: ReentrancyPolicy, f: impl ReactorFactory<E>) -> &mut Self
+        where
+            E: Event + Clone
+    {
+        let f = Arc::new(f);
+        let mut triggered = Triggered::new(policy);
+        self.add_systems(PostUpdate, move |mut commands: Commands,
+                                           mut er: EventReader<E>,
+                                           reactors: Query<(), With<Reactor>>| {
+            triggered.process(&mut commands, &reactors, er.read().cloned(), |event| {
+                let f = Arc::clone(&f);
+                Reactor::schedule(move |task| f(task, event))
+            });
+        })
+    }
+
+    fn add_reactor_on_enter<S, Fut>(
+        &mut self,
+        state: S,
+        policy: ReentrancyPolicy,
+        f: impl Fn(ReactiveTask) -> Fut + Send + Sync + 'static,
+    ) -> &mut Self
+        where
+            S: States,
+            Fut: Future + 'static
+    {
+        let f = Arc::new(f);
+        let entered_state = state.clone();
+        let mut triggered = Triggered::new(policy);
+        self
+            .add_event::<StateEntered<S>>()
+            .add_systems(OnEnter(state.clone()), move |mut ew: EventWriter<StateEntered<S>>| {
+                ew.send(StateEntered(entered_state.clone()));
+            })
+            .add_systems(PostUpdate, move |mut commands: Commands,
+                                           mut er: EventReader<StateEntered<S>>,
+                                           reactors: Query<(), With<Reactor>>| {
+                let entered = er.read().filter(|entered| entered.0 == state).map(|_| ());
+                triggered.process(&mut commands, &reactors, entered, |_| {
+                    let f = Arc::clone(&f);
+                    Reactor::schedule(move |task| f(task))
+                });
+            })
+    }
+
+    fn add_reactor_when<M, Fut>(
+        &mut self,
+        condition: impl Condition<M>,
+        policy: ReentrancyPolicy,
+        f: impl Fn(ReactiveTask) -> Fut + Send + Sync + 'static,
+    ) -> &mut Self
+        where
+            Fut: Future + 'static
+    {
+        let f = Arc::new(f);
+        let mut triggered = Triggered::new(policy);
+        self.add_systems(PostUpdate, condition.pipe(move |In(fired): In<bool>,
+                                                          mut commands: Commands,
+                                                          reactors: Query<(), With<Reactor>>| {
+            triggered.process(&mut commands, &reactors, fired.then_some(()), |_| {
+                let f = Arc::clone(&f);
+                Reactor::schedule(move |task| f(task))
+            });
+        }))
+    }
+}
+
+/// Sent when the state becomes the value registered by [`TriggerReactorExtension::add_reactor_on_enter`].
+#[derive(Event)]
+struct StateEntered<S: States>(S);
+
+struct Triggered<In> {
+    policy: ReentrancyPolicy,
+    running: Vec<Entity>,
+    queue: VecDeque<In>,
+}
+
+impl<In> Triggered<In> {
+    #[inline]
+    const fn new(policy: ReentrancyPolicy) -> Self {
+        Self {
+            policy,
+            running: Vec::new(),
+            queue: VecDeque::new(),
+        }
+    }
+
+    fn process(
+        &mut self,
+        commands: &mut Commands,
+        reactors: &Query<(), With<Reactor>>,
+        fired: impl IntoIterator<Item=In>,
+        create_reactor: impl Fn(In) -> Reactor,
+    ) {
+        self.running.retain(|entity| reactors.contains(*entity));
+        let mut fired = fired.into_iter();
+        match self.policy {
+            ReentrancyPolicy::Queue => {
+                self.queue.extend(fired);
+                if self.running.is_empty() {
+                    if let Some(input) = self.queue.pop_front() {
+                        self.running.push(commands.spawn(create_reactor(input)).id());
+                    }
+                }
+            }
+            ReentrancyPolicy::Ignore => {
+                if let Some(input) = fired.next() {
+                    if self.running.is_empty() {
+                        self.running.push(commands.spawn(create_reactor(input)).id());
+                    }
+                }
+            }
+            ReentrancyPolicy::Restart => {
+                if let Some(input) = fired.last() {
+                    for entity in self.running.drain(..) {
+                        if let Some(reactor) = commands.get_entity(entity) {
+                            reactor.despawn_recursive();
+                        }
+                    }
+                    self.running.push(commands.spawn(create_reactor(input)).id());
+                }
+            }
+            ReentrancyPolicy::Parallel => {
+                for input in fired {
+                    self.running.push(commands.spawn(create_reactor(input)).id());
+                }
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::{App, Update};
+    use bevy::prelude::{NextState, resource_exists, ResMut, States};
+    use bevy_test_helper::event::TestEvent1;
+    use bevy_test_helper::resource::count::Count;
+    use bevy_test_helper::resource::DirectResourceControl;
+
+    use crate::action::{delay, once, wait};
+    use crate::extension::{ReentrancyPolicy, TriggerReactorExtension};
+    use crate::prelude::Then;
+    use crate::test_util::reactor_count;
+    use crate::tests::{increment_count, test_app, TestResource};
+
+    #[derive(States, Eq, PartialEq, Default, Copy, Clone, Hash, Debug)]
+    enum TestState {
+        #[default]
+        Phase1,
+        Phase2,
+    }
+
+    fn app_with_event_reactor(policy: ReentrancyPolicy) -> App {
+        let mut app = test_app();
+        app.add_reactor_on_event::<TestEvent1>(policy, |task, _| async move {
+            task.will(Update, increment_count().then(wait::until(|| false))).await;
+        });
+        app
+    }
+
+    #[test]
+    fn parallel_spawns_reactor_for_each_event() {
+        let mut app = app_with_event_reactor(ReentrancyPolicy::Parallel);
+        app.world.send_event(TestEvent1);
+        app.world.send_event(TestEvent1);
+        app.update();
+        app.update();
+        assert_eq!(reactor_count(&mut app), 2);
+        app.assert_resource_eq(Count(2));
+
+        app.world.send_event(TestEvent1);
+        app.update();
+        assert_eq!(reactor_count(&mut app), 3);
+    }
+
+    #[test]
+    fn ignore_events_while_running() {
+        let mut app = app_with_event_reactor(ReentrancyPolicy::Ignore);
+        app.world.send_event(TestEvent1);
+        app.world.send_event(TestEvent1);
+        app.update();
+        app.world.send_event(TestEvent1);
+        app.update();
+        app.update();
+        assert_eq!(reactor_count(&mut app), 1);
+        app.assert_resource_eq(Count(1));
+    }
+
+    #[test]
+    fn restart_running_reactor() {
+        let mut app = app_with_event_reactor(ReentrancyPolicy::Restart);
+        app.world.send_event(TestEvent1);
+        app.update();
+        app.update();
+        app.assert_resource_eq(Count(1));
+
+        app.world.send_event(TestEvent1);
+        app.update();
+        app.update();
+        assert_eq!(reactor_count(&mut app), 1);
+        app.assert_resource_eq(Count(2));
+    }
+
+    #[test]
+    fn queue_events_until_previous_finished() {
+        let mut app = test_app();
+        app.add_reactor_on_event::<TestEvent1>(ReentrancyPolicy::Queue, |task, _| async move {
+            task.will(Update, increment_count().then(delay::frames().with(3))).await;
+        });
+        app.world.send_event(TestEvent1);
+        app.world.send_event(TestEvent1);
+        app.update();
+        for _ in 0..3 {
+            app.update();
+            assert!(reactor_count(&mut app) <= 1);
+            app.assert_resource_eq(Count(1));
+        }
+        for _ in 0..3 {
+            app.update();
+            assert!(reactor_count(&mut app) <= 1);
+        }
+        app.assert_resource_eq(Count(2));
+    }
+
+    #[test]
+    fn spawn_reactor_on_enter_state() {
+        let mut app = test_app();
+        app
+            .init_state::<TestState>()
+            .add_reactor_on_enter(TestState::Phase2, ReentrancyPolicy::Parallel, |task| async move {
+                task.will(Update, increment_count()).await;
+            });
+        app.update();
+        app.update();
+        app.assert_resource_eq(Count(0));
+
+        app.world.resource_mut::<NextState<TestState>>().set(TestState::Phase2);
+        app.update();
+        app.update();
+        app.assert_resource_eq(Count(1));
+
+        app.world.resource_mut::<NextState<TestState>>().set(TestState::Phase1);
+        app.update();
+        app.world.resource_mut::<NextState<TestState>>().set(TestState::Phase2);
+        app.update();
+        app.update();
+        app.assert_resource_eq(Count(2));
+    }
+
+    #[test]
+    fn spawn_reactor_when_condition_is_true() {
+        let mut app = test_app();
+        app.add_reactor_when(resource_exists::<TestResource>, ReentrancyPolicy::Ignore, |task| async move {
+            task.will(Update, once::run(|mut count: ResMut<Count>| {
+                count.increment();
+            })).await;
+        });
+        app.update();
+        app.update();
+        app.assert_resource_eq(Count(0));
+
+        app.world.insert_resource(TestResource);
+        app.update();
+        app.update();
+        app.assert_resource_eq(Count(1));
+        app.update();
+        app.update();
+        app.assert_resource_eq(Count(2));
+    }
+}
@@ -13,12 +13,17 @@
 use bevy::app::{App, Last, MainScheduleOrder, Plugin, PostStartup};
 use bevy::ecs::schedule::ScheduleLabel;
 use bevy::hierarchy::DespawnRecursiveExt;
-use bevy::prelude::{Entity, QueryState, World};
+use bevy::prelude::{Added, Entity, QueryState, Resource, World};
+use bevy::utils::Instant;
 
-use crate::reactor::Reactor;
+use crate::action::parallel::ParallelJobs;
+use crate::diagnostics::FlurxStats;
+use crate::reactor::{DebugTracking, FinishPolicy, Reactor, ReactorFailed, resolve_keys, StartMode, StartSchedule};
+use crate::runner::RunnerBudget;
 use crate::world_ptr::WorldPtr;
 
 pub mod extension;
+pub mod diagnostics;
 pub mod task;
 pub mod action;
 pub mod runner;
@@ -36,14 +41,17 @@ pub mod prelude {
         action::switch::*,
         action::through::{through, Through},
         action::wait::Either,
+        diagnostics::FlurxDiagnosticsPlugin,
         extension::*,
         FlurxPlugin,
-        reactor::Reactor,
+        reactor::{FinishPolicy, FlurxDebugPlugin, KeyPolicy, Reactor, ReactorDebugInfo, ReactorFailed, Reactors, ReactorsMut, RunnerDebugInfo, StartMode, StartSchedule},
         runner::*,
         task::ReactiveTask,
     };
     #[cfg(feature = "effect")]
-    pub use crate::action::effect::AsyncFunctor;
+    pub use crate::action::effect::{AsyncFunctor, EffectError};
+    #[cfg(all(feature = "effect", feature = "tokio"))]
+    pub use crate::action::effect::tokio::FlurxTokioPlugin;
     #[cfg(feature = "record")]
     pub use crate::action::record::{
         EditRecordResult,
@@ -67,12 +75,55 @@ mod selector;
 mod test_util;
 
 /// Provides the async systems.
-pub struct FlurxPlugin;
+///
+/// ## Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_flurx::prelude::*;
+///
+/// App::new()
+///     .add_plugins((
+///         DefaultPlugins,
+///         FlurxPlugin {
+///             catch_panics: true,
+///             ..default()
+///         },
+///     ));
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct FlurxPlugin {
+    /// If `true`, a panic inside an action or the processing flow of a [`Reactor`] is caught instead of crashing the app.
+    ///
+    /// The [`Reactor`] is cancelled, its cancellation handlers are called,
+    /// and [`ReactorFailed`](crate::prelude::ReactorFailed) is sent.
+    ///
+    /// The default is `false`.
+    pub catch_panics: bool,
+
+    /// Limits how many runners are run each time a schedule runs.
+    ///
+    /// The default is unlimited.
+    pub budget: RunnerBudget,
+}
+
+/// The settings given by [`FlurxPlugin`].
+#[derive(Resource, Debug, Default, Clone)]
+pub(crate) struct FlurxSettings {
+    pub catch_panics: bool,
+    pub budget: RunnerBudget,
+}
 
 impl Plugin for FlurxPlugin {
     #[inline]
     fn build(&self, app: &mut App) {
         app
+            .insert_resource(FlurxSettings {
+                catch_panics: self.catch_panics,
+                budget: self.budget,
+            })
+            .init_non_send_resource::<ParallelJobs>()
+            .add_event::<ReactorFailed>()
             .init_schedule(RunReactor)
             .add_systems(PostStartup, initialize_reactors)
             .add_systems(RunReactor, run_reactors);
@@ -89,40 +140,113 @@ struct RunReactor;
 
 fn initialize_reactors(
     world: &mut World,
-    reactors: &mut QueryState<&mut Reactor>,
+    reactors: &mut QueryState<(Entity, &mut Reactor)>,
+    added: &mut QueryState<(Entity, &Reactor), Added<Reactor>>,
 ) {
+    resolve_keys(world, added);
     let world_ptr = WorldPtr::new(world);
-    for mut reactor in reactors.iter_mut(world) {
-        if reactor.initialized {
+    let mut finished = Vec::new();
+    let mut pending = Vec::new();
+    for (entity, mut reactor) in reactors.iter_mut(world) {
+        // Reactors that start in the next frame are picked up at the end of the first frame.
+        if reactor.picked_up || reactor.held || matches!(reactor.start_mode, StartMode::NextFrame) {
             continue;
         }
-        reactor.initialized = true;
-        reactor.run_sync(world_ptr);
+        if pick_up(entity, &mut reactor, world_ptr, &mut pending) {
+            finished.push((entity, reactor.finish_policy));
+        }
     }
+    start_later(world, pending);
+    finish_reactors(world, finished);
 }
 
 fn run_reactors(
     world: &mut World,
     reactors: &mut QueryState<(Entity, &mut Reactor)>,
+    added: &mut QueryState<(Entity, &Reactor), Added<Reactor>>,
 ) {
+    let start = world.contains_resource::<FlurxStats>().then(Instant::now);
+    resolve_keys(world, added);
     let world_ptr = WorldPtr::new(world);
-    let mut entities = Vec::with_capacity(reactors.iter(world).len());
+    let mut finished = Vec::with_capacity(reactors.iter(world).len());
+    let mut pending = Vec::new();
+    let debug = world.contains_resource::<DebugTracking>();
+    let mut debug_infos = Vec::new();
     for (entity, mut reactor) in reactors.iter_mut(world) {
-        if !reactor.initialized {
-            if reactor.run_sync(world_ptr) || reactor.run_sync(world_ptr) {
-                entities.push(entity);
-            } else {
-                reactor.initialized = true;
+        if reactor.finished() || reactor.held {
+            continue;
+        }
+        let finish = if !reactor.picked_up {
+            pick_up(entity, &mut reactor, world_ptr, &mut pending)
+        } else if reactor.initialized {
+            reactor.run_sync(world_ptr)
+        } else {
+            false
+        };
+        if finish {
+            finished.push((entity, reactor.finish_policy));
+        }
+        if debug {
+            if let Some(info) = reactor.take_changed_debug_info() {
+                debug_infos.push((entity, info));
             }
-        } else if reactor.run_sync(world_ptr) {
-            entities.push(entity);
         }
     }
-    for entity in entities {
-        world.entity_mut(entity).despawn_recursive();
+    if let (Some(start), Some(mut stats)) = (start, world.get_resource_mut::<FlurxStats>()) {
+        stats.run_reactors_time += start.elapsed();
+    }
+    for (entity, info) in debug_infos {
+        if let Some(mut entity_mut) = world.get_entity_mut(entity) {
+            entity_mut.insert(info);
+        }
+    }
+    start_later(world, pending);
+    finish_reactors(world, finished);
+}
+
+/// Starts the reactor if its [`StartMode`] is [`StartMode::Immediate`],
+/// otherwise pushes it to `pending` to start it on its schedule later.
+///
+/// Returns `true` if the reactor has finished on start.
+fn pick_up(
+    entity: Entity,
+    reactor: &mut Reactor,
+    world: WorldPtr,
+    pending: &mut Vec<(Entity, StartSchedule)>,
+) -> bool {
+    reactor.picked_up = true;
+    reactor.set_entity(entity);
+    match reactor.start_mode.start_schedule() {
+        Some(schedule) => {
+            pending.push((entity, schedule));
+            false
+        }
+        None => reactor.start(world),
     }
 }
 
+fn start_later(world: &mut World, pending: Vec<(Entity, StartSchedule)>) {
+    for (entity, schedule) in pending {
+        schedule.push_pending(world, entity);
+    }
+}
+
+/// Applies [`FinishPolicy`] of the finished reactors.
+pub(crate) fn finish_reactors(world: &mut World, finished: Vec<(Entity, FinishPolicy)>) {
+    for (entity, policy) in finished {
+        let Some(mut entity_mut) = world.get_entity_mut(entity) else {
+            continue;
+        };
+        match policy {
+            FinishPolicy::RemoveComponent => {
+                entity_mut.remove::<Reactor>();
+            }
+            FinishPolicy::Despawn => entity_mut.despawn(),
+            FinishPolicy::DespawnRecursive => entity_mut.despawn_recursive(),
+            FinishPolicy::Keep => {}
+        }
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -163,10 +287,14 @@ mod tests {
     pub struct NumAct(pub usize);
 
     pub fn test_app() -> App {
+        test_app_with(FlurxPlugin::default())
+    }
+
+    pub fn test_app_with(flurx: FlurxPlugin) -> App {
         let mut app = App::new();
         app.add_plugins((
             BevyTestHelperPlugin,
-            FlurxPlugin,
+            flurx,
             InputPlugin,
             TimePlugin,
             FrameCountPlugin
@@ -1,36 +1,69 @@
+use std::borrow::Cow;
 use std::future::Future;
+use std::panic::{AssertUnwindSafe, catch_unwind};
 
-use bevy::prelude::Component;
+use bevy::prelude::{Component, Entity, Event, State, States};
+use bevy::utils::HashSet;
+#[cfg(feature = "tracing")]
+use bevy::utils::tracing::{debug, field, info_span, Span};
 
-use crate::runner::CancellationToken;
+use crate::FlurxSettings;
+use crate::runner::{CancellationToken, fail_reactor};
 use crate::task::ReactiveTask;
 use crate::world_ptr::WorldPtr;
 
+pub use debug::{FlurxDebugPlugin, ReactorDebugInfo, RunnerDebugInfo};
+pub(crate) use debug::DebugTracking;
+pub use keyed::KeyPolicy;
+pub(crate) use keyed::resolve_keys;
+pub use reactors::{Reactors, ReactorsMut};
+pub use start::{StartMode, StartSchedule};
+pub(crate) use start::start_pending_reactors;
+
+use crate::reactor::keyed::ReactorKey;
+
+mod debug;
+mod keyed;
+mod reactors;
+mod start;
+
 /// [`Reactor`] represents the asynchronous processing flow.
 ///
 /// This structure is created by [`Reactor::schedule`] or [`ScheduleReactor`](crate::prelude::ScheduleReactor).
 ///
 /// Remove this component if you want to interrupt the processing flow.
 ///
-/// After all scheduled processes have completed, the entity attached to this component
-/// and it's children will be despawn.
+/// After all scheduled processes have completed or been cancelled, the entity attached to this component
+/// and it's children will be despawn by default.
+/// This can be changed with [`Reactor::on_finish`].
+///
+/// If you want to bind the processing flow to an existing entity without despawning it,
+/// use [`AttachReactor`](crate::prelude::AttachReactor) instead.
 #[derive(Component)]
 pub struct Reactor {
     pub(crate) scheduler: flurx::Scheduler<'static, 'static, WorldPtr>,
     pub(crate) initialized: bool,
+    pub(crate) picked_up: bool,
+    pub(crate) start_mode: StartMode,
+    pub(crate) finish_policy: FinishPolicy,
+    name: Option<Cow<'static, str>>,
+    tags: HashSet<Cow<'static, str>>,
+    key: Option<ReactorKey>,
+    pub(crate) held: bool,
+    #[cfg(feature = "tracing")]
+    started: bool,
     token: CancellationToken,
 }
 
 impl Reactor {
     /// Create new [`Reactor`].
     ///
-    /// The scheduled [`Reactor`] will be run and initialized at `RunReactor` schedule(and also initialized at [`PostStartup`](bevy::prelude::PostStartup)) ,
+    /// The scheduled [`Reactor`] is picked up at the end of the frame in which it is spawned
+    /// (or in [`PostStartup`](bevy::prelude::PostStartup) if it is spawned during the startup),
+    /// and started according to its [`StartMode`].
     ///
-    /// It is recommended to spawn this structure at [`Update`](bevy::prelude::Update) or [`Startup`](bevy::prelude::Startup)
-    /// to reduce the delay until initialization.
-    ///
-    /// If you spawn on another [`ScheduleLabel`](bevy::ecs::schedule::ScheduleLabel), 
-    /// you can spawn and initialize at the same time by using [`ScheduleReactor`](crate::prelude::ScheduleReactor).
+    /// If you want to start it in the system that spawns it,
+    /// use [`ScheduleReactor`](crate::prelude::ScheduleReactor) instead.
     ///
     /// ## Examples
     ///
@@ -50,6 +83,8 @@ impl Reactor {
     {
         let mut scheduler = flurx::Scheduler::new();
         let token = CancellationToken::default();
+        #[cfg(feature = "tracing")]
+        token.set_span(info_span!("reactor", name = field::Empty));
         let t1 = token.clone();
         scheduler.schedule(move |task| async move {
             f(ReactiveTask {
@@ -61,16 +96,268 @@ impl Reactor {
             scheduler,
             token,
             initialized: false,
+            picked_up: false,
+            start_mode: StartMode::default(),
+            finish_policy: FinishPolicy::default(),
+            name: None,
+            tags: HashSet::new(),
+            key: None,
+            held: false,
+            #[cfg(feature = "tracing")]
+            started: false,
         }
     }
 
+    /// Sets what to do with the entity attached to this [`Reactor`]
+    /// after the processing flow has finished or been cancelled.
+    ///
+    /// The default is [`FinishPolicy::DespawnRecursive`].
+    ///
+    /// ## Examples
+    ///
+    /// ```no_run
+    /// use bevy::prelude::*;
+    /// use bevy_flurx::prelude::*;
+    ///
+    /// fn spawn_reactor(mut commands: Commands){
+    ///     commands.spawn(Reactor::schedule(|task| async move{
+    ///         task.will(Update, once::run(||{})).await;
+    ///     }).on_finish(FinishPolicy::RemoveComponent));
+    /// }
+    /// ```
+    #[inline]
+    pub fn on_finish(mut self, policy: FinishPolicy) -> Self {
+        self.finish_policy = policy;
+        self
+    }
+
+    /// Sets when this [`Reactor`] is started after it is picked up.
+    ///
+    /// The default is [`StartMode::Immediate`].
+    ///
+    /// ## Examples
+    ///
+    /// ```no_run
+    /// use bevy::prelude::*;
+    /// use bevy_flurx::prelude::*;
+    ///
+    /// fn spawn_reactor(mut commands: Commands){
+    ///     commands.spawn(Reactor::schedule(|task| async move{
+    ///         task.will(Update, once::run(||{})).await;
+    ///     }).start_mode(StartMode::NextFrame));
+    /// }
+    /// ```
+    #[inline]
+    pub fn start_mode(mut self, mode: StartMode) -> Self {
+        self.start_mode = mode;
+        self
+    }
+
+    /// Names this [`Reactor`].
+    ///
+    /// Named reactors can be looked up with [`Reactors`] and cancelled with [`ReactorsMut`].
+    ///
+    /// ## Examples
+    ///
+    /// ```no_run
+    /// use bevy::prelude::*;
+    /// use bevy_flurx::prelude::*;
+    ///
+    /// fn spawn_reactor(mut commands: Commands){
+    ///     commands.spawn(Reactor::schedule(|task| async move{
+    ///         task.will(Update, once::run(||{})).await;
+    ///     }).named("intro_cutscene"));
+    /// }
+    /// ```
+    #[inline]
+    pub fn named(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        let name = name.into();
+        #[cfg(feature = "tracing")]
+        if let Some(span) = self.token.span() {
+            span.record("name", name.as_ref());
+        }
+        self.name = Some(name);
+        self
+    }
+
+    /// Adds a tag to this [`Reactor`].
+    ///
+    /// A reactor can have several tags, and reactors with the same tag can be cancelled together with [`ReactorsMut`].
+    ///
+    /// ## Examples
+    ///
+    /// ```no_run
+    /// use bevy::prelude::*;
+    /// use bevy_flurx::prelude::*;
+    ///
+    /// fn spawn_reactor(mut commands: Commands){
+    ///     commands.spawn(Reactor::schedule(|task| async move{
+    ///         task.will(Update, once::run(||{})).await;
+    ///     }).tagged("combat").tagged("boss"));
+    /// }
+    /// ```
+    #[inline]
+    pub fn tagged(mut self, tag: impl Into<Cow<'static, str>>) -> Self {
+        self.tags.insert(tag.into());
+        self
+    }
+
+    /// Gives this [`Reactor`] a key so that only one reactor with the same key runs at a time.
+    ///
+    /// `policy` decides what happens if a reactor with the same key is already running
+    /// when this reactor is picked up.
+    ///
+    /// ## Examples
+    ///
+    /// ```no_run
+    /// use bevy::prelude::*;
+    /// use bevy_flurx::prelude::*;
+    ///
+    /// fn on_interact(mut commands: Commands){
+    ///     commands.spawn(Reactor::schedule(|task| async move{
+    ///         task.will(Update, wait::input::just_pressed().with(KeyCode::Enter)).await;
+    ///     }).keyed("dialogue", KeyPolicy::Drop));
+    /// }
+    /// ```
+    #[inline]
+    pub fn keyed(mut self, key: impl Into<Cow<'static, str>>, policy: KeyPolicy) -> Self {
+        self.key = Some(ReactorKey::new(key.into(), policy));
+        self
+    }
+
+    /// Returns the name given by [`Reactor::named`].
+    #[inline]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns `true` if this [`Reactor`] has been tagged with `tag`.
+    #[inline]
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.contains(tag)
+    }
+
+    /// Returns the tags given by [`Reactor::tagged`].
+    #[inline]
+    pub fn tags(&self) -> impl Iterator<Item=&str> {
+        self.tags.iter().map(|tag| tag.as_ref())
+    }
+
+    /// Returns `true` if this [`Reactor`] has neither finished nor been cancelled.
+    #[inline]
+    pub fn is_active(&self) -> bool {
+        !self.finished() && !self.token.is_cancellation_requested()
+    }
+
+    /// Cancels this [`Reactor`] when [`State<S>`] is no longer `state`.
+    ///
+    /// The cancellation handlers of the running actions are called, just as when it is cancelled manually.
+    /// Note that if the state is not `state` when the reactor first runs, it is cancelled immediately.
+    ///
+    /// ## Examples
+    ///
+    /// ```no_run
+    /// use bevy::prelude::*;
+    /// use bevy_flurx::prelude::*;
+    ///
+    /// #[derive(States, Eq, PartialEq, Copy, Clone, Hash, Default, Debug)]
+    /// enum GameState {
+    ///     #[default]
+    ///     Menu,
+    ///     Playing,
+    /// }
+    ///
+    /// fn spawn_reactor(mut commands: Commands){
+    ///     commands.spawn(Reactor::schedule(|task| async move{
+    ///         task.will(Update, wait::until(|| false)).await;
+    ///     }).scoped_to(GameState::Playing));
+    /// }
+    /// ```
+    pub fn scoped_to<S: States>(self, state: S) -> Self {
+        self.token.add_scope(move |world| {
+            !world
+                .get_resource::<State<S>>()
+                .is_some_and(|now| now.get() != &state)
+        });
+        self
+    }
+
+    /// Create new [`Reactor`] whose lifetime is tied to `owner`.
+    ///
+    /// The reactor is cancelled when `owner` is despawned.
+    pub(crate) fn attach<F>(owner: Entity, f: impl FnOnce(ReactiveTask, Entity) -> F + 'static) -> Reactor
+        where F: Future
+    {
+        let reactor = Reactor::schedule(move |task| f(task, owner));
+        reactor.token.set_owner(owner);
+        reactor
+    }
+
+    /// Runs the processing flow for the first time.
+    ///
+    /// Returns `true` if it has already finished or been cancelled.
+    #[inline]
+    pub(crate) fn start(&mut self, world: WorldPtr) -> bool {
+        self.picked_up = true;
+        self.initialized = true;
+        self.run_sync(world)
+    }
+
     #[inline(always)]
     pub(crate) fn run_sync(&mut self, world: WorldPtr) -> bool {
-        if self.token.is_cancellation_requested() {
+        if self.token.is_cancellation_requested() || self.token.out_of_scope(world.as_mut()) {
             return true;
         }
 
-        #[cfg(all(not(target_arch = "wasm32"), feature = "tokio"))]
+        #[cfg(feature = "tracing")]
+        let span = self.token.span().cloned().unwrap_or_else(Span::none);
+        #[cfg(feature = "tracing")]
+        let _entered = span.enter();
+        #[cfg(feature = "tracing")]
+        if !self.started {
+            self.started = true;
+            debug!(frame = crate::runner::frame_count(world.as_mut()), "reactor started");
+        }
+
+        let catch_panics = world
+            .as_mut()
+            .get_resource::<FlurxSettings>()
+            .is_some_and(|settings| settings.catch_panics);
+        if catch_panics {
+            if let Err(payload) = catch_unwind(AssertUnwindSafe(|| self.poll(world))) {
+                fail_reactor(world.as_mut(), "reactor", &self.token, payload);
+                return true;
+            }
+        } else {
+            self.poll(world);
+        }
+
+        let finished = self.scheduler.not_exists_reactor();
+        if finished{
+            self.token.set_finished();
+            #[cfg(feature = "tracing")]
+            debug!(frame = crate::runner::frame_count(world.as_mut()), "reactor finished");
+        }
+        finished || self.token.is_cancellation_requested()
+    }
+
+    /// Polls the processing flow until it waits for an action.
+    fn poll(&mut self, world: WorldPtr) {
+        #[cfg(all(not(target_arch = "wasm32"), feature = "tokio", feature = "effect"))]
+        {
+            use async_compat::CompatExt;
+            let handle = world
+                .as_mut()
+                .get_resource::<crate::action::effect::tokio::TokioRuntime>()
+                .map(|runtime| runtime.handle().clone());
+            if let Some(handle) = handle {
+                let _guard = handle.enter();
+                pollster::block_on(self.scheduler.run(world));
+            } else {
+                pollster::block_on(self.scheduler.run(world).compat());
+            }
+        }
+        #[cfg(all(not(target_arch = "wasm32"), feature = "tokio", not(feature = "effect")))]
         {
             use async_compat::CompatExt;
             pollster::block_on(self.scheduler.run(world).compat());
@@ -79,13 +366,63 @@ impl Reactor {
         {
             pollster::block_on(self.scheduler.run(world));
         }
+    }
 
-        let finished = self.scheduler.not_exists_reactor();
-        if finished{
-            self.token.set_finished();
-        }
-        finished || self.token.is_cancellation_requested()
+    /// Returns [`ReactorDebugInfo`] if the running actions have changed since the last call.
+    #[inline]
+    pub(crate) fn take_changed_debug_info(&self) -> Option<ReactorDebugInfo> {
+        self.token.take_changed_runners().map(|runners| ReactorDebugInfo {
+            name: self.name.clone(),
+            runners,
+        })
     }
+
+    #[inline(always)]
+    pub(crate) fn set_entity(&self, entity: Entity) {
+        self.token.set_reactor_entity(entity);
+    }
+
+    #[inline(always)]
+    pub(crate) fn finished(&self) -> bool {
+        self.token.finished_reactor()
+    }
+}
+
+/// Specifies what to do with the entity attached to [`Reactor`]
+/// after the processing flow has finished or been cancelled.
+///
+/// Please see [`Reactor::on_finish`].
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum FinishPolicy {
+    /// Removes only [`Reactor`] from the entity.
+    RemoveComponent,
+
+    /// Despawns the entity, but not its children.
+    Despawn,
+
+    /// Despawns the entity and its children.
+    #[default]
+    DespawnRecursive,
+
+    /// Keeps [`Reactor`] on the entity.
+    ///
+    /// The kept [`Reactor`] is never run again.
+    Keep,
+}
+
+/// Sent when an action or the processing flow of a [`Reactor`] panics
+/// while [`FlurxPlugin::catch_panics`](crate::FlurxPlugin::catch_panics) is enabled.
+///
+/// The failed [`Reactor`] has been cancelled.
+#[derive(Event, Debug, Clone, Eq, PartialEq)]
+pub struct ReactorFailed {
+    /// The entity of the failed [`Reactor`].
+    ///
+    /// This is `None` if the action was run with a [`CancellationToken`] that does not belong to a [`Reactor`].
+    pub entity: Option<Entity>,
+
+    /// The panic message.
+    pub message: String,
 }
 
 impl Drop for Reactor {
@@ -107,12 +444,35 @@ unsafe impl Sync for Reactor {}
 mod tests {
     use bevy::app::{Startup, Update};
     use bevy::ecs::system::RunSystemOnce;
-    use bevy::prelude::{Commands, Entity, Query, ResMut, Resource, With};
+    use bevy::prelude::{BuildWorldChildren, Commands, Component, Entity, NextState, Query, ResMut, Resource, States, With};
+    use bevy_test_helper::resource::count::Count as HelperCount;
     use bevy_test_helper::resource::DirectResourceControl;
 
     use crate::action::{delay, wait};
-    use crate::prelude::{BoxedRunners, Reactor};
-    use crate::tests::test_app;
+    use crate::prelude::{BoxedRunners, FinishPolicy, Reactor};
+    use crate::test_util::test;
+    use crate::tests::{increment_count, test_app};
+
+    #[derive(States, Eq, PartialEq, Default, Copy, Clone, Hash, Debug)]
+    enum TestState {
+        #[default]
+        Phase1,
+        Phase2,
+    }
+
+    #[derive(Component)]
+    struct Child;
+
+    fn spawn_reactor_with_child(app: &mut bevy::app::App, policy: FinishPolicy) -> Entity {
+        app.world
+            .spawn(Reactor::schedule(|task| async move {
+                task.will(Update, increment_count()).await;
+            }).on_finish(policy))
+            .with_children(|parent| {
+                parent.spawn(Child);
+            })
+            .id()
+    }
 
     #[derive(Resource, Debug, Default, Eq, PartialEq)]
     struct Count(usize);
@@ -156,4 +516,104 @@ mod tests {
         assert!(app.world.query::<&Reactor>().get_single(&app.world).is_err());
         assert_eq!(app.world.non_send_resource::<BoxedRunners<Update>>().0.len(), 0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn remove_component_after_finished() {
+        let mut app = test_app();
+        let entity = spawn_reactor_with_child(&mut app, FinishPolicy::RemoveComponent);
+        app.update();
+        app.update();
+        assert!(app.world.get_entity(entity).is_some());
+        assert!(app.world.get::<Reactor>(entity).is_none());
+        assert!(app.world.query::<&Child>().get_single(&app.world).is_ok());
+    }
+
+    #[test]
+    fn despawn_without_children_after_finished() {
+        let mut app = test_app();
+        let entity = spawn_reactor_with_child(&mut app, FinishPolicy::Despawn);
+        app.update();
+        app.update();
+        assert!(app.world.get_entity(entity).is_none());
+        assert!(app.world.query::<&Child>().get_single(&app.world).is_ok());
+    }
+
+    #[test]
+    fn despawn_recursive_after_finished() {
+        let mut app = test_app();
+        let entity = spawn_reactor_with_child(&mut app, FinishPolicy::DespawnRecursive);
+        app.update();
+        app.update();
+        assert!(app.world.get_entity(entity).is_none());
+        assert!(app.world.query::<&Child>().get_single(&app.world).is_err());
+    }
+
+    #[test]
+    fn keep_reactor_after_finished() {
+        let mut app = test_app();
+        let entity = spawn_reactor_with_child(&mut app, FinishPolicy::Keep);
+        for _ in 0..5 {
+            app.update();
+        }
+        app.assert_resource_eq(HelperCount(1));
+        assert!(app.world.get::<Reactor>(entity).is_some());
+        assert!(app.world.query::<&Child>().get_single(&app.world).is_ok());
+    }
+
+    #[test]
+    fn apply_policy_after_cancelled() {
+        let mut app = test_app();
+        app.init_resource::<Count>();
+        let entity = app.world.spawn(Reactor::schedule(|task| async move {
+            task.will(Update, wait::until(|mut count: ResMut<Count>| {
+                count.0 += 1;
+                false
+            })).await;
+        }).on_finish(FinishPolicy::RemoveComponent)).id();
+        app.update();
+        app.world.get::<Reactor>(entity).unwrap().token.cancel();
+        app.update();
+        app.assert_resource_eq(Count(1));
+        assert!(app.world.get_entity(entity).is_some());
+        assert!(app.world.get::<Reactor>(entity).is_none());
+    }
+
+    #[test]
+    fn cancel_scoped_reactor_on_state_exit() {
+        let mut app = test_app();
+        app.init_resource::<Count>();
+        app.init_state::<TestState>();
+        let entity = app.world.spawn(Reactor::schedule(|task| async move {
+            task.will(Update, wait::until(|mut count: ResMut<Count>| {
+                count.0 += 1;
+                false
+            })).await;
+        }).scoped_to(TestState::Phase1)).id();
+        app.update();
+        app.update();
+        app.assert_resource_eq(Count(2));
+
+        app.world.resource_mut::<NextState<TestState>>().set(TestState::Phase2);
+        for _ in 0..5 {
+            app.update();
+        }
+        app.assert_resource_eq(Count(2));
+        assert!(app.world.get_entity(entity).is_none());
+    }
+
+    #[test]
+    fn call_cancel_handles_on_state_exit() {
+        let mut app = test_app();
+        app.init_state::<TestState>();
+        app.world.spawn(Reactor::schedule(|task| async move {
+            task.will(Update, test::count_on_cancelled()).await;
+        }).scoped_to(TestState::Phase1));
+        app.update();
+        app.update();
+        app.assert_resource_eq(HelperCount(0));
+
+        app.world.resource_mut::<NextState<TestState>>().set(TestState::Phase2);
+        app.update();
+        app.assert_resource_eq(HelperCount(1));
+    }
+}
@@ -0,0 +1,157 @@
+use std::borrow::Cow;
+
+use bevy::app::{App, Plugin};
+use bevy::ecs::schedule::ScheduleLabel;
+use bevy::prelude::{Component, Resource};
+use bevy::utils::intern::Interned;
+
+/// Inserts [`ReactorDebugInfo`] into the entity of every [`Reactor`](crate::prelude::Reactor).
+///
+/// Without this plugin, the running actions are not tracked at all.
+///
+/// ## Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_flurx::prelude::*;
+///
+/// App::new()
+///     .add_plugins((
+///         DefaultPlugins,
+///         FlurxPlugin::default(),
+///         FlurxDebugPlugin,
+///     ));
+/// ```
+#[derive(Default)]
+pub struct FlurxDebugPlugin;
+
+impl Plugin for FlurxDebugPlugin {
+    #[inline]
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DebugTracking>();
+    }
+}
+
+/// Exists while the running actions are tracked for [`ReactorDebugInfo`].
+#[derive(Resource, Default)]
+pub(crate) struct DebugTracking;
+
+/// Shows what [`Reactor`](crate::prelude::Reactor) is currently waiting on.
+///
+/// This component is inserted into the entity of every [`Reactor`](crate::prelude::Reactor)
+/// if [`FlurxDebugPlugin`] is added,
+/// and is updated at the end of each frame in which the running actions change.
+///
+/// Giving actions names with [`ActionSeed::named`](crate::prelude::ActionSeed::named)
+/// makes it easier to tell them apart.
+///
+/// ## Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_flurx::prelude::*;
+///
+/// App::new()
+///     .add_plugins((
+///         DefaultPlugins,
+///         FlurxPlugin::default(),
+///         FlurxDebugPlugin,
+///     ))
+///     .add_systems(Update, print_stuck_reactors);
+///
+/// fn print_stuck_reactors(reactors: Query<&ReactorDebugInfo>){
+///     for info in reactors.iter(){
+///         for runner in &info.runners {
+///             info!("{:?} is waiting on `{}` in {:?} since frame {}", info.name, runner.action, runner.schedule, runner.started_frame);
+///         }
+///     }
+/// }
+/// ```
+#[derive(Component, Debug, Clone, Default)]
+pub struct ReactorDebugInfo {
+    /// The name given by [`Reactor::named`](crate::prelude::Reactor::named).
+    pub name: Option<Cow<'static, str>>,
+
+    /// The actions that are currently running.
+    pub runners: Vec<RunnerDebugInfo>,
+}
+
+/// Shows an action that is currently running on [`Reactor`](crate::prelude::Reactor).
+///
+/// Please see [`ReactorDebugInfo`].
+#[derive(Debug, Clone)]
+pub struct RunnerDebugInfo {
+    /// The schedule the action is running on.
+    pub schedule: Interned<dyn ScheduleLabel>,
+
+    /// The debug name of the action.
+    pub action: Cow<'static, str>,
+
+    /// The value of [`FrameCount`](bevy::core::FrameCount) when the action started.
+    ///
+    /// This is `0` if [`FrameCountPlugin`](bevy::core::FrameCountPlugin) is not added.
+    pub started_frame: u32,
+}
+
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::{First, Update};
+    use bevy::ecs::schedule::ScheduleLabel;
+
+    use bevy::app::App;
+
+    use crate::action::{once, wait};
+    use crate::prelude::{FlurxDebugPlugin, Reactor, ReactorDebugInfo};
+    use crate::tests::test_app;
+
+    fn debug_app() -> App {
+        let mut app = test_app();
+        app.add_plugins(FlurxDebugPlugin);
+        app
+    }
+
+    #[test]
+    fn show_running_action() {
+        let mut app = debug_app();
+        let entity = app.world.spawn(Reactor::schedule(|task| async move {
+            task.will(Update, wait::until(|| false).named("wait for door")).await;
+        }).named("cutscene")).id();
+        app.update();
+        app.update();
+
+        let info = app.world.get::<ReactorDebugInfo>(entity).unwrap();
+        assert_eq!(info.name.as_deref(), Some("cutscene"));
+        assert_eq!(info.runners.len(), 1);
+        assert_eq!(info.runners[0].action, "wait for door");
+        assert_eq!(info.runners[0].schedule, Update.intern());
+        assert_eq!(info.runners[0].started_frame, 0);
+    }
+
+    #[test]
+    fn update_after_action_finished() {
+        let mut app = debug_app();
+        let entity = app.world.spawn(Reactor::schedule(|task| async move {
+            task.will(Update, once::run(|| {})).await;
+            task.will(First, wait::until(|| false)).await;
+        })).id();
+        app.update();
+
+        let info = app.world.get::<ReactorDebugInfo>(entity).unwrap();
+        assert_eq!(info.name, None);
+        assert_eq!(info.runners.len(), 1);
+        assert_eq!(info.runners[0].schedule, First.intern());
+        assert!(info.runners[0].action.ends_with("WaitRunner"), "{}", info.runners[0].action);
+        assert_eq!(info.runners[0].started_frame, 1);
+    }
+
+    #[test]
+    fn no_debug_info_without_plugin() {
+        let mut app = test_app();
+        let entity = app.world.spawn(Reactor::schedule(|task| async move {
+            task.will(Update, wait::until(|| false)).await;
+        })).id();
+        app.update();
+        assert!(app.world.get::<ReactorDebugInfo>(entity).is_none());
+    }
+}
@@ -0,0 +1,261 @@
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use bevy::ecs::component::Tick;
+use bevy::prelude::{Added, Entity, QueryState, Resource, World};
+use bevy::utils::{HashMap, HashSet};
+
+use crate::prelude::{CancellationToken, Reactor};
+
+static SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+/// Specifies what to do if a [`Reactor`] with the same key is already running.
+///
+/// Please see [`Reactor::keyed`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum KeyPolicy {
+    /// Cancels the running reactor and starts the new one.
+    Replace,
+
+    /// Cancels the new reactor without running it.
+    Drop,
+
+    /// Starts the new reactor after the running one has finished.
+    ///
+    /// If several reactors are waiting for the same key, they are started one by one in the order they were keyed.
+    Wait,
+}
+
+pub(crate) struct ReactorKey {
+    key: Cow<'static, str>,
+    policy: KeyPolicy,
+    sequence: u64,
+}
+
+impl ReactorKey {
+    #[inline]
+    pub(crate) fn new(key: Cow<'static, str>, policy: KeyPolicy) -> Self {
+        Self {
+            key,
+            policy,
+            sequence: SEQUENCE.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+}
+
+/// The entities of the keyed reactors that are waiting to start or running.
+#[derive(Resource)]
+pub(crate) struct KeyedReactors {
+    entities: Vec<Entity>,
+    /// The change tick at which the added reactors were last checked.
+    ///
+    /// The systems' own ticks would miss the reactors spawned while the reactors are running.
+    checked: Tick,
+}
+
+impl Default for KeyedReactors {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            entities: Vec::new(),
+            checked: Tick::new(0),
+        }
+    }
+}
+
+/// Decides which of the keyed reactors that have not been picked up yet can start.
+///
+/// Only the reactors added since the last call and the keyed reactors found before are checked,
+/// and nothing is allocated while there are no keyed reactors.
+/// Reactors that have to wait are marked as `held`,
+/// and those that have to be dropped are cancelled.
+pub(crate) fn resolve_keys(world: &mut World, added: &mut QueryState<(Entity, &Reactor), Added<Reactor>>) {
+    let checked = world.get_resource_or_insert_with(KeyedReactors::default).checked;
+    let new_keyed = world.last_change_tick_scope(checked, |world| {
+        added
+            .iter(world)
+            .filter_map(|(entity, reactor)| reactor.key.is_some().then_some(entity))
+            .collect::<Vec<_>>()
+    });
+    let checked = world.increment_change_tick();
+    let mut keyed = world.resource_mut::<KeyedReactors>();
+    keyed.checked = checked;
+    keyed.entities.extend(new_keyed);
+    if keyed.entities.is_empty() {
+        return;
+    }
+    let mut entities = std::mem::take(&mut keyed.entities);
+
+    let mut running = HashMap::<Cow<'static, str>, Vec<CancellationToken>>::new();
+    let mut pending = Vec::new();
+    let mut release = Vec::new();
+    entities.retain(|entity| {
+        let Some(reactor) = world.get::<Reactor>(*entity) else {
+            return false;
+        };
+        let Some(key) = reactor.key.as_ref() else {
+            return false;
+        };
+        if !reactor.is_active() {
+            if reactor.held {
+                release.push(*entity);
+            }
+            false
+        } else if reactor.picked_up {
+            running
+                .entry(key.key.clone())
+                .or_default()
+                .push(reactor.token.clone());
+            true
+        } else {
+            pending.push((*entity, key.key.clone(), key.policy, key.sequence, reactor.token.clone(), reactor.held));
+            true
+        }
+    });
+    world.resource_mut::<KeyedReactors>().entities.extend(entities);
+    pending.sort_by_key(|(.., sequence, _, _)| *sequence);
+
+    let mut held = Vec::new();
+    let mut waiting = HashSet::new();
+    for (entity, key, policy, _, token, was_held) in pending {
+        let tokens = running.entry(key.clone()).or_default();
+        tokens.retain(|token| !token.is_cancellation_requested());
+        let hold = match policy {
+            KeyPolicy::Replace => {
+                for token in tokens.drain(..) {
+                    token.cancel();
+                }
+                false
+            }
+            KeyPolicy::Drop if !tokens.is_empty() => {
+                token.cancel();
+                if was_held {
+                    release.push(entity);
+                }
+                continue;
+            }
+            KeyPolicy::Wait if !tokens.is_empty() || waiting.contains(&key) => {
+                waiting.insert(key);
+                true
+            }
+            _ => false,
+        };
+        if hold != was_held {
+            if hold {
+                held.push(entity);
+            } else {
+                release.push(entity);
+            }
+        }
+        if !hold {
+            tokens.push(token);
+        }
+    }
+
+    // Mutable access is taken only for the reactors whose state changes.
+    for (entities, hold) in [(held, true), (release, false)] {
+        for entity in entities {
+            if let Some(mut reactor) = world.get_mut::<Reactor>(entity) {
+                reactor.held = hold;
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::Update;
+    use bevy::prelude::{Commands, ResMut};
+    use bevy_test_helper::resource::count::Count;
+    use bevy_test_helper::resource::DirectResourceControl;
+
+    use crate::action::{delay, once};
+    use crate::prelude::{KeyPolicy, Reactor, Then};
+    use crate::test_util::reactor_count;
+    use crate::tests::test_app;
+
+    fn dialogue(policy: KeyPolicy) -> Reactor {
+        Reactor::schedule(|task| async move {
+            task.will(Update, once::run(|mut count: ResMut<Count>| {
+                count.increment();
+            }).then(delay::frames().with(3))).await;
+        }).keyed("dialogue", policy)
+    }
+
+    #[test]
+    fn replace_running_reactor() {
+        let mut app = test_app();
+        app.world.spawn(dialogue(KeyPolicy::Replace));
+        app.update();
+        app.assert_resource_eq(Count(1));
+
+        app.world.spawn(dialogue(KeyPolicy::Replace));
+        app.update();
+        assert_eq!(reactor_count(&mut app), 1);
+        app.update();
+        app.assert_resource_eq(Count(2));
+    }
+
+    #[test]
+    fn drop_new_reactor() {
+        let mut app = test_app();
+        app.world.spawn(dialogue(KeyPolicy::Drop));
+        app.update();
+        app.world.spawn(dialogue(KeyPolicy::Drop));
+        app.world.spawn(dialogue(KeyPolicy::Drop));
+        app.update();
+        assert_eq!(reactor_count(&mut app), 1);
+        for _ in 0..5 {
+            app.update();
+        }
+        app.assert_resource_eq(Count(1));
+        assert_eq!(reactor_count(&mut app), 0);
+    }
+
+    #[test]
+    fn wait_running_reactor() {
+        let mut app = test_app();
+        app.world.spawn(dialogue(KeyPolicy::Wait));
+        app.world.spawn(dialogue(KeyPolicy::Wait));
+        app.world.spawn(dialogue(KeyPolicy::Wait));
+        let mut counts = Vec::new();
+        for _ in 0..20 {
+            app.update();
+            counts.push(app.world.resource::<Count>().0);
+        }
+        assert_eq!(counts.iter().filter(|count| **count == 1).count(), 5);
+        assert_eq!(counts.iter().filter(|count| **count == 2).count(), 5);
+        assert_eq!(counts.last(), Some(&3));
+        assert_eq!(reactor_count(&mut app), 0);
+    }
+
+    #[test]
+    fn different_keys_run_in_parallel() {
+        let mut app = test_app();
+        app.world.spawn(dialogue(KeyPolicy::Drop));
+        app.world.spawn(Reactor::schedule(|task| async move {
+            task.will(Update, once::run(|mut count: ResMut<Count>| {
+                count.increment();
+            })).await;
+        }).keyed("other", KeyPolicy::Drop));
+        app.update();
+        app.assert_resource_eq(Count(2));
+    }
+
+    #[test]
+    fn resolve_reactor_spawned_by_reactor() {
+        let mut app = test_app();
+        app.world.spawn(dialogue(KeyPolicy::Drop));
+        app.world.spawn(Reactor::schedule(|task| async move {
+            task.will(Update, once::run(|mut commands: Commands| {
+                commands.spawn(dialogue(KeyPolicy::Drop));
+            })).await;
+        }));
+        for _ in 0..6 {
+            app.update();
+        }
+        app.assert_resource_eq(Count(1));
+        assert_eq!(reactor_count(&mut app), 0);
+    }
+}
@@ -0,0 +1,261 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::{Entity, Query};
+
+use crate::prelude::Reactor;
+
+/// A [`SystemParam`] to look up and check [`Reactor`]s by name or tag.
+///
+/// Names and tags are given by [`Reactor::named`] and [`Reactor::tagged`].
+///
+/// This only reads the reactors, so systems using it can run in parallel with the other readers.
+/// Use [`ReactorsMut`] to cancel them.
+///
+/// ## Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_flurx::prelude::*;
+///
+/// fn is_intro_playing(reactors: Reactors) -> bool{
+///     reactors.is_active("intro")
+/// }
+///
+/// App::new()
+///     .add_plugins(FlurxPlugin::default())
+///     .add_systems(Update, (|| {}).run_if(not(is_intro_playing)));
+/// ```
+#[derive(SystemParam)]
+pub struct Reactors<'w, 's> {
+    reactors: Query<'w, 's, (Entity, &'static Reactor)>,
+}
+
+impl<'w, 's> Reactors<'w, 's> {
+    /// Returns the entity of the [`Reactor`] named `name`.
+    ///
+    /// If several reactors have the same name, one of them is returned.
+    #[inline]
+    pub fn find(&self, name: &str) -> Option<Entity> {
+        find(self.reactors.iter(), name)
+    }
+
+    /// Returns the entities of the [`Reactor`]s tagged with `tag`.
+    #[inline]
+    pub fn tagged<'a>(&'a self, tag: &'a str) -> impl Iterator<Item=Entity> + 'a {
+        tagged(self.reactors.iter(), tag)
+    }
+
+    /// Returns `true` if the [`Reactor`] named `name` exists
+    /// and has neither finished nor been cancelled.
+    #[inline]
+    pub fn is_active(&self, name: &str) -> bool {
+        is_active(self.reactors.iter(), name)
+    }
+
+    /// Returns `true` if any [`Reactor`] tagged with `tag` has neither finished nor been cancelled.
+    #[inline]
+    pub fn is_any_tagged_active(&self, tag: &str) -> bool {
+        is_any_tagged_active(self.reactors.iter(), tag)
+    }
+}
+
+/// A [`SystemParam`] to look up, check, and cancel [`Reactor`]s by name or tag.
+///
+/// Unlike [`Reactors`], this takes mutable access to all reactors.
+///
+/// ## Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_flurx::prelude::*;
+///
+/// #[derive(Event)]
+/// struct BossDied;
+///
+/// fn spawn_combat_script(mut commands: Commands){
+///     commands.spawn(Reactor::schedule(|task| async move{
+///         task.will(Update, wait::until(|| false)).await;
+///     }).tagged("combat"));
+/// }
+///
+/// fn cancel_combat_scripts(mut reactors: ReactorsMut){
+///     reactors.cancel_tagged("combat");
+/// }
+///
+/// App::new()
+///     .add_plugins(FlurxPlugin::default())
+///     .add_event::<BossDied>()
+///     .add_systems(Startup, spawn_combat_script)
+///     .add_systems(Update, cancel_combat_scripts.run_if(on_event::<BossDied>()));
+/// ```
+#[derive(SystemParam)]
+pub struct ReactorsMut<'w, 's> {
+    reactors: Query<'w, 's, (Entity, &'static mut Reactor)>,
+}
+
+impl<'w, 's> ReactorsMut<'w, 's> {
+    /// Returns the entity of the [`Reactor`] named `name`.
+    ///
+    /// If several reactors have the same name, one of them is returned.
+    #[inline]
+    pub fn find(&self, name: &str) -> Option<Entity> {
+        find(self.reactors.iter(), name)
+    }
+
+    /// Returns the entities of the [`Reactor`]s tagged with `tag`.
+    #[inline]
+    pub fn tagged<'a>(&'a self, tag: &'a str) -> impl Iterator<Item=Entity> + 'a {
+        tagged(self.reactors.iter(), tag)
+    }
+
+    /// Returns `true` if the [`Reactor`] named `name` exists
+    /// and has neither finished nor been cancelled.
+    #[inline]
+    pub fn is_active(&self, name: &str) -> bool {
+        is_active(self.reactors.iter(), name)
+    }
+
+    /// Returns `true` if any [`Reactor`] tagged with `tag` has neither finished nor been cancelled.
+    #[inline]
+    pub fn is_any_tagged_active(&self, tag: &str) -> bool {
+        is_any_tagged_active(self.reactors.iter(), tag)
+    }
+
+    /// Requests to cancel all [`Reactor`]s named `name`.
+    ///
+    /// Returns the number of reactors requested to cancel.
+    #[inline]
+    pub fn cancel(&mut self, name: &str) -> usize {
+        self.cancel_if(|reactor| reactor.name() == Some(name))
+    }
+
+    /// Requests to cancel all [`Reactor`]s tagged with `tag`.
+    ///
+    /// Returns the number of reactors requested to cancel.
+    #[inline]
+    pub fn cancel_tagged(&mut self, tag: &str) -> usize {
+        self.cancel_if(|reactor| reactor.has_tag(tag))
+    }
+
+    fn cancel_if(&mut self, predicate: impl Fn(&Reactor) -> bool) -> usize {
+        let mut cancelled = 0;
+        for (_, reactor) in self.reactors.iter_mut() {
+            if predicate(&reactor) && reactor.is_active() {
+                reactor.token.cancel();
+                cancelled += 1;
+            }
+        }
+        cancelled
+    }
+}
+
+fn find<'a>(mut reactors: impl Iterator<Item=(Entity, &'a Reactor)>, name: &str) -> Option<Entity> {
+    reactors.find_map(|(entity, reactor)| (reactor.name() == Some(name)).then_some(entity))
+}
+
+fn tagged<'a>(reactors: impl Iterator<Item=(Entity, &'a Reactor)> + 'a, tag: &'a str) -> impl Iterator<Item=Entity> + 'a {
+    reactors.filter_map(move |(entity, reactor)| reactor.has_tag(tag).then_some(entity))
+}
+
+fn is_active<'a>(mut reactors: impl Iterator<Item=(Entity, &'a Reactor)>, name: &str) -> bool {
+    reactors.any(|(_, reactor)| reactor.name() == Some(name) && reactor.is_active())
+}
+
+fn is_any_tagged_active<'a>(mut reactors: impl Iterator<Item=(Entity, &'a Reactor)>, tag: &str) -> bool {
+    reactors.any(|(_, reactor)| reactor.has_tag(tag) && reactor.is_active())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::Update;
+    use bevy::ecs::schedule::{LogLevel, ScheduleBuildSettings};
+    use bevy::ecs::system::RunSystemOnce;
+    use bevy::prelude::{In, IntoSystemConfigs, ResMut, Schedule, World};
+    use bevy_test_helper::resource::count::Count;
+    use bevy_test_helper::resource::DirectResourceControl;
+
+    use crate::action::wait;
+    use crate::prelude::{Reactor, Reactors, ReactorsMut};
+    use crate::tests::test_app;
+
+    fn counting_reactor() -> Reactor {
+        Reactor::schedule(|task| async move {
+            task.will(Update, wait::until(|mut count: ResMut<Count>| {
+                count.increment();
+                false
+            })).await;
+        })
+    }
+
+    #[test]
+    fn find_by_name() {
+        let mut app = test_app();
+        let entity = app.world.spawn(counting_reactor().named("intro")).id();
+        app.world.spawn(counting_reactor().named("outro"));
+        app.update();
+
+        assert_eq!(app.world.run_system_once(|reactors: Reactors| reactors.find("intro")), Some(entity));
+        assert!(app.world.run_system_once(|reactors: Reactors| reactors.is_active("intro")));
+        assert!(app.world.run_system_once(|reactors: Reactors| reactors.find("credits")).is_none());
+    }
+
+    #[test]
+    fn cancel_by_name() {
+        let mut app = test_app();
+        app.world.spawn(counting_reactor().named("intro"));
+        app.update();
+        app.assert_resource_eq(Count(1));
+
+        let cancelled = app.world.run_system_once(|mut reactors: ReactorsMut| reactors.cancel("intro"));
+        assert_eq!(cancelled, 1);
+        assert!(!app.world.run_system_once(|reactors: Reactors| reactors.is_active("intro")));
+        for _ in 0..3 {
+            app.update();
+        }
+        app.assert_resource_eq(Count(1));
+        assert!(app.world.run_system_once(|reactors: Reactors| reactors.find("intro")).is_none());
+    }
+
+    #[test]
+    fn cancel_by_tag() {
+        let mut app = test_app();
+        app.world.spawn(counting_reactor().tagged("combat"));
+        app.world.spawn(counting_reactor().tagged("combat").tagged("boss"));
+        app.world.spawn(counting_reactor().tagged("ui"));
+        app.update();
+        app.assert_resource_eq(Count(3));
+
+        let cancelled = app.world.run_system_once_with("combat", |In(tag): In<&'static str>, mut reactors: ReactorsMut| {
+            reactors.cancel_tagged(tag)
+        });
+        assert_eq!(cancelled, 2);
+        assert!(!app.world.run_system_once(|reactors: Reactors| reactors.is_any_tagged_active("boss")));
+        assert!(app.world.run_system_once(|reactors: Reactors| reactors.is_any_tagged_active("ui")));
+        app.update();
+        app.assert_resource_eq(Count(4));
+        assert_eq!(app.world.run_system_once(|reactors: Reactors| reactors.tagged("ui").count()), 1);
+    }
+
+    fn is_ambiguous<M>(systems: impl IntoSystemConfigs<M>) -> bool {
+        let mut world = World::new();
+        let mut schedule = Schedule::default();
+        schedule.set_build_settings(ScheduleBuildSettings {
+            ambiguity_detection: LogLevel::Error,
+            ..Default::default()
+        });
+        schedule.add_systems(systems);
+        schedule.initialize(&mut world).is_err()
+    }
+
+    #[test]
+    fn readers_do_not_conflict() {
+        assert!(!is_ambiguous((
+            |reactors: Reactors| assert!(!reactors.is_active("intro")),
+            |reactors: Reactors| assert!(!reactors.is_active("outro")),
+        )));
+        assert!(is_ambiguous((
+            |reactors: Reactors| assert!(!reactors.is_active("intro")),
+            |mut reactors: ReactorsMut| assert_eq!(reactors.cancel("outro"), 0),
+        )));
+    }
+}
@@ -0,0 +1,244 @@
+use std::fmt::{Debug, Formatter};
+
+use bevy::app::First;
+use bevy::ecs::schedule::ScheduleLabel;
+use bevy::prelude::{Entity, Resource, World};
+use bevy::utils::HashMap;
+use bevy::utils::intern::Interned;
+
+use crate::finish_reactors;
+use crate::prelude::Reactor;
+use crate::runner::prepare_runners;
+use crate::world_ptr::WorldPtr;
+
+/// Specifies when [`Reactor`] is started, that is, when its processing flow is first run
+/// and its first action is registered.
+///
+/// A reactor is picked up at the end of the frame in which it is spawned,
+/// or in [`PostStartup`](bevy::prelude::PostStartup) if it is spawned during the startup.
+/// This does not depend on how the reactor was spawned.
+/// An action registered on start first runs the next time its schedule runs.
+///
+/// Please see [`Reactor::start_mode`].
+#[derive(Debug, Default, Copy, Clone)]
+pub enum StartMode {
+    /// Starts [`Reactor`] as soon as it is picked up.
+    ///
+    /// The actions of a reactor spawned during the startup run from the first frame,
+    /// and those of a reactor spawned later run from the next frame.
+    ///
+    /// This is the default.
+    #[default]
+    Immediate,
+
+    /// Starts [`Reactor`] in [`First`] of the frame after the one in which it is spawned.
+    ///
+    /// Unlike [`StartMode::Immediate`], the actions of a reactor spawned during the startup also run from the next frame.
+    NextFrame,
+
+    /// Starts [`Reactor`] the next time the schedule runs after it is picked up,
+    /// before the actions on that schedule are run.
+    ///
+    /// Created by [`StartMode::schedule`].
+    Schedule(StartSchedule),
+}
+
+impl StartMode {
+    /// Creates [`StartMode::Schedule`] that starts [`Reactor`] on `label`.
+    ///
+    /// ## Examples
+    ///
+    /// ```no_run
+    /// use bevy::prelude::*;
+    /// use bevy_flurx::prelude::*;
+    ///
+    /// fn spawn_reactor(mut commands: Commands){
+    ///     commands.spawn(Reactor::schedule(|task| async move{
+    ///         // This runs in the same `PostUpdate` as the reactor is started.
+    ///         task.will(PostUpdate, once::run(||{})).await;
+    ///     }).start_mode(StartMode::schedule(PostUpdate)));
+    /// }
+    /// ```
+    #[inline]
+    pub fn schedule<L: ScheduleLabel>(label: L) -> Self {
+        Self::Schedule(StartSchedule::new(label))
+    }
+
+    #[inline]
+    pub(crate) fn start_schedule(&self) -> Option<StartSchedule> {
+        match self {
+            Self::Immediate => None,
+            Self::NextFrame => Some(StartSchedule::new(First)),
+            Self::Schedule(schedule) => Some(*schedule),
+        }
+    }
+}
+
+/// The schedule on which [`Reactor`] is started.
+///
+/// Please see [`StartMode::schedule`].
+#[derive(Copy, Clone)]
+pub struct StartSchedule {
+    label: Interned<dyn ScheduleLabel>,
+    prepare: fn(&mut World, Interned<dyn ScheduleLabel>),
+}
+
+impl StartSchedule {
+    #[inline]
+    fn new<L: ScheduleLabel>(label: L) -> Self {
+        Self {
+            label: label.intern(),
+            prepare: prepare_runners::<L>,
+        }
+    }
+
+    /// Returns the label of the schedule.
+    #[inline]
+    pub fn label(&self) -> Interned<dyn ScheduleLabel> {
+        self.label
+    }
+
+    /// Makes sure the schedule starts the pending reactors, and adds `entity` to them.
+    pub(crate) fn push_pending(&self, world: &mut World, entity: Entity) {
+        (self.prepare)(world, self.label);
+        world
+            .get_resource_or_insert_with(PendingStarts::default)
+            .0
+            .entry(self.label)
+            .or_default()
+            .push(entity);
+    }
+}
+
+impl Debug for StartSchedule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("StartSchedule").field(&self.label).finish()
+    }
+}
+
+/// The reactors that have been picked up and are waiting to be started on each schedule.
+#[derive(Resource, Default)]
+struct PendingStarts(HashMap<Interned<dyn ScheduleLabel>, Vec<Entity>>);
+
+/// Starts the reactors waiting to be started on `label`.
+pub(crate) fn start_pending_reactors(world: &mut World, label: Interned<dyn ScheduleLabel>) {
+    let Some(entities) = world
+        .get_resource_mut::<PendingStarts>()
+        .and_then(|mut pending| pending.0.remove(&label)) else {
+        return;
+    };
+    let world_ptr = WorldPtr::new(world);
+    let mut finished = Vec::new();
+    for entity in entities {
+        let Some(mut reactor) = world.get_mut::<Reactor>(entity) else {
+            continue;
+        };
+        if reactor.start(world_ptr) {
+            finished.push((entity, reactor.finish_policy));
+        }
+    }
+    finish_reactors(world, finished);
+}
+
+
+#[cfg(test)]
+mod tests {
+    use bevy::app::{PostUpdate, Startup, Update};
+    use bevy::prelude::{Commands, Local, ResMut};
+    use bevy_test_helper::resource::count::Count;
+    use bevy_test_helper::resource::DirectResourceControl;
+
+    use crate::action::once;
+    use crate::prelude::{Reactor, StartMode};
+    use crate::tests::{increment_count, test_app};
+
+    fn count_up_reactor(mode: StartMode) -> Reactor {
+        Reactor::schedule(|task| async move {
+            task.will(Update, increment_count()).await;
+        }).start_mode(mode)
+    }
+
+    #[test]
+    fn immediate_starts_in_first_frame_if_spawned_on_startup() {
+        let mut app = test_app();
+        app.add_systems(Startup, |mut commands: Commands| {
+            commands.spawn(count_up_reactor(StartMode::Immediate));
+        });
+        app.world.spawn(count_up_reactor(StartMode::Immediate));
+        app.update();
+        app.assert_resource_eq(Count(2));
+    }
+
+    #[test]
+    fn next_frame_starts_in_next_frame_if_spawned_on_startup() {
+        let mut app = test_app();
+        app.add_systems(Startup, |mut commands: Commands| {
+            commands.spawn(count_up_reactor(StartMode::NextFrame));
+        });
+        app.world.spawn(count_up_reactor(StartMode::NextFrame));
+        app.update();
+        app.assert_resource_eq(Count(0));
+        app.update();
+        app.assert_resource_eq(Count(2));
+    }
+
+    #[test]
+    fn start_in_next_frame_if_spawned_on_update() {
+        for mode in [StartMode::Immediate, StartMode::NextFrame] {
+            let mut app = test_app();
+            app.add_systems(Update, move |mut commands: Commands, mut spawned: Local<bool>| {
+                if !*spawned {
+                    *spawned = true;
+                    commands.spawn(count_up_reactor(mode));
+                }
+            });
+            app.update();
+            app.assert_resource_eq(Count(0));
+            app.update();
+            app.assert_resource_eq(Count(1));
+        }
+    }
+
+    #[test]
+    fn start_on_schedule() {
+        let mut app = test_app();
+        app.world.spawn(Reactor::schedule(|task| async move {
+            task.will(PostUpdate, increment_count()).await;
+        }).start_mode(StartMode::schedule(PostUpdate)));
+        app.update();
+        app.assert_resource_eq(Count(1));
+    }
+
+    #[test]
+    fn actions_on_earlier_schedule_run_in_next_frame() {
+        let mut app = test_app();
+        app.world.spawn(count_up_reactor(StartMode::schedule(PostUpdate)));
+        app.update();
+        app.assert_resource_eq(Count(0));
+        app.update();
+        app.assert_resource_eq(Count(1));
+    }
+
+    #[test]
+    fn run_only_once_on_start() {
+        let mut app = test_app();
+        app.world.spawn(Reactor::schedule(|task| async move {
+            task.will(Update, once::run(|mut count: ResMut<Count>| {
+                count.increment();
+            })).await;
+            task.will(Update, increment_count()).await;
+        }));
+        app.update();
+        app.assert_resource_eq(Count(1));
+        app.update();
+        app.assert_resource_eq(Count(2));
+    }
+
+    #[test]
+    fn despawn_if_finished_on_start() {
+        let mut app = test_app();
+        app.world.spawn(Reactor::schedule(|_| async move {}));
+        app.update();
+        assert_eq!(app.world.query::<&Reactor>().iter(&app.world).len(), 0);
+    }
+}
@@ -1,10 +1,25 @@
 //! `Runner` defines what does the actual processing of the action.
 
+use std::any::{Any, type_name};
+use std::borrow::Cow;
 use std::marker::PhantomData;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::time::Duration;
 
+use bevy::core::FrameCount;
 use bevy::ecs::schedule::ScheduleLabel;
-use bevy::prelude::{Schedule, Schedules, World};
+use bevy::prelude::{IntoSystemConfigs, Schedule, Schedules, World};
+use bevy::utils::Instant;
 use bevy::utils::intern::Interned;
+use bevy::utils::tracing::error;
+#[cfg(feature = "tracing")]
+use bevy::utils::tracing::{debug, info_span, Span};
+
+use crate::action::parallel::{ParallelJobs, run_parallel_jobs};
+use crate::diagnostics::FlurxStats;
+use crate::FlurxSettings;
+use crate::prelude::ReactorFailed;
+use crate::reactor::{DebugTracking, RunnerDebugInfo, start_pending_reactors};
 
 pub use cancellation_token::{CancellationId, CancellationToken};
 pub use output::Output;
@@ -27,24 +42,48 @@ pub trait Runner {
 /// The boxed runner.
 ///
 /// It is created by [`Action`](crate::prelude::Action).
-#[repr(transparent)]
-pub struct BoxedRunner(Option<Box<dyn Runner>>);
+pub struct BoxedRunner {
+    runner: Option<Box<dyn Runner>>,
+    name: Cow<'static, str>,
+    #[cfg(feature = "tracing")]
+    span: Span,
+}
 
 impl BoxedRunner {
     #[inline]
-    pub(crate) fn new(runner: impl Runner + 'static) -> Self {
-        Self(Some(Box::new(runner)))
+    pub(crate) fn new<R: Runner + 'static>(runner: R) -> Self {
+        let name = type_name::<R>();
+        Self {
+            runner: Some(Box::new(runner)),
+            name: Cow::Borrowed(name.split('<').next().unwrap_or(name)),
+            #[cfg(feature = "tracing")]
+            span: Span::none(),
+        }
+    }
+
+    /// Returns the debug name of the action this runner was created from.
+    ///
+    /// It is the name given by [`ActionSeed::named`](crate::prelude::ActionSeed::named),
+    /// or the type name of the runner if not named.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[inline]
+    pub(crate) fn set_name(&mut self, name: Cow<'static, str>) {
+        self.name = name;
     }
 }
 
 impl Runner for BoxedRunner {
     #[inline(always)]
     fn run(&mut self, world: &mut World, token: &CancellationToken) -> bool {
-        if let Some(mut runner) = self.0.take() {
+        if let Some(mut runner) = self.runner.take() {
             if runner.run(world, token) {
                 true
             } else {
-                self.0.replace(runner);
+                self.runner.replace(runner);
                 false
             }
         } else {
@@ -53,8 +92,51 @@ impl Runner for BoxedRunner {
     }
 }
 
-#[repr(transparent)]
-pub(crate) struct BoxedRunners<L: Send + Sync>(pub Vec<(BoxedRunner, CancellationToken)>, PhantomData<L>);
+/// Limits how many runners are run each time a schedule runs.
+///
+/// When the budget is exhausted, the remaining runners are skipped,
+/// and they are run first the next time the schedule runs.
+/// At least one runner is always run.
+///
+/// Please see [`FlurxPlugin::budget`](crate::FlurxPlugin::budget).
+///
+/// ## Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use bevy::prelude::*;
+/// use bevy_flurx::prelude::*;
+///
+/// App::new()
+///     .add_plugins((
+///         DefaultPlugins,
+///         FlurxPlugin {
+///             budget: RunnerBudget {
+///                 max_runners: Some(500),
+///                 max_time: Some(Duration::from_millis(2)),
+///             },
+///             ..default()
+///         },
+///     ));
+/// ```
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct RunnerBudget {
+    /// The maximum number of runners run per schedule run.
+    pub max_runners: Option<usize>,
+
+    /// The maximum time spent running runners per schedule run.
+    pub max_time: Option<Duration>,
+}
+
+impl RunnerBudget {
+    #[inline]
+    fn exhausted(&self, runs: usize, start: Option<Instant>) -> bool {
+        self.max_runners.is_some_and(|max_runners| max_runners <= runs)
+            || self.max_time.zip(start).is_some_and(|(max_time, start)| max_time <= start.elapsed())
+    }
+}
+
+pub(crate) struct BoxedRunners<L: Send + Sync>(pub Vec<(BoxedRunner, CancellationToken, Option<u64>)>, Interned<dyn ScheduleLabel>, PhantomData<L>);
 
 pub(crate) fn initialize_runner<Label>(
     world: &mut World,
@@ -64,17 +146,47 @@ pub(crate) fn initialize_runner<Label>(
 )
     where Label: ScheduleLabel
 {
+    #[cfg(feature = "tracing")]
+    let runner = {
+        let mut runner = runner;
+        runner.span = info_span!(parent: token.span_id(), "action", action = %runner.name, schedule = ?label);
+        runner.span.in_scope(|| debug!(frame = frame_count(world), "action started"));
+        runner
+    };
+    let runner_id = world.contains_resource::<DebugTracking>().then(|| token.start_runner(RunnerDebugInfo {
+        schedule: label.intern(),
+        action: runner.name.clone(),
+        started_frame: frame_count(world),
+    }));
+    if let Some(mut stats) = world.get_resource_mut::<FlurxStats>() {
+        stats.runners_started += 1;
+    }
+    prepare_runners::<Label>(world, label.intern());
     if let Some(mut runners) = world.get_non_send_resource_mut::<BoxedRunners<Label>>() {
-        runners.0.push((runner, token));
-    } else {
-        world.insert_non_send_resource(BoxedRunners::<Label>(vec![(runner, token)], PhantomData));
-        let Some(mut schedules) = world.get_resource_mut::<Schedules>() else {
-            return;
-        };
+        runners.0.push((runner, token, runner_id));
+    }
+}
 
-        let schedule = initialize_schedule(&mut schedules, label.intern());
-        schedule.add_systems(run_runners::<Label>);
+/// Adds the systems that run the runners to the schedule if they have not been added yet.
+///
+/// The reactors waiting to be started on the schedule are started before the runners are run.
+pub(crate) fn prepare_runners<Label>(world: &mut World, label: Interned<dyn ScheduleLabel>)
+    where Label: ScheduleLabel
+{
+    if world.contains_non_send::<BoxedRunners<Label>>() {
+        return;
     }
+    world.insert_non_send_resource(BoxedRunners::<Label>(Vec::new(), label, PhantomData));
+    let Some(mut schedules) = world.get_resource_mut::<Schedules>() else {
+        return;
+    };
+
+    let schedule = initialize_schedule(&mut schedules, label);
+    schedule.add_systems((
+        move |world: &mut World| start_pending_reactors(world, label),
+        run_runners::<Label>,
+        move |world: &mut World| run_parallel_jobs(world, label),
+    ).chain());
 }
 
 #[inline]
@@ -88,20 +200,106 @@ pub(crate) fn initialize_schedule(schedules: &mut Schedules, schedule_label: Int
 
 fn run_runners<L: Send + Sync + 'static>(world: &mut World) {
     if let Some(mut runners) = world.remove_non_send_resource::<BoxedRunners<L>>() {
-        runners.0.retain_mut(|(runner, token)| {
-            if token.finished_reactor(){
+        ParallelJobs::set_running_schedule(world, Some(runners.1));
+        let start = world.contains_resource::<FlurxStats>().then(Instant::now);
+        let (catch_panics, budget) = world
+            .get_resource::<FlurxSettings>()
+            .map(|settings| (settings.catch_panics, settings.budget))
+            .unwrap_or_default();
+        let budget_start = budget.max_time.map(|_| Instant::now());
+        let len = runners.0.len();
+        let mut visited = 0;
+        let mut reached = 0;
+        runners.0.retain_mut(|(runner, token, runner_id)| {
+            if 0 < visited && budget.exhausted(visited, budget_start) {
+                return true;
+            }
+            visited += 1;
+            #[cfg(feature = "tracing")]
+            let span = runner.span.clone();
+            #[cfg(feature = "tracing")]
+            let _entered = span.enter();
+            let running = if token.finished_reactor() {
                 false
-            } else if token.is_cancellation_requested() {
+            } else if token.is_cancellation_requested() || token.out_of_scope(world) {
+                #[cfg(feature = "tracing")]
+                debug!(frame = frame_count(world), "action cancelled");
                 token.call_cancel_handles(world);
                 false
+            } else if catch_panics {
+                match catch_unwind(AssertUnwindSafe(|| runner.run(world, token))) {
+                    Ok(finished) => {
+                        #[cfg(feature = "tracing")]
+                        if finished {
+                            debug!(frame = frame_count(world), "action finished");
+                        }
+                        !finished
+                    }
+                    Err(payload) => {
+                        fail_reactor(world, &format!("action `{}`", runner.name), token, payload);
+                        false
+                    }
+                }
             } else {
-                !runner.run(world, token)
+                let finished = runner.run(world, token);
+                #[cfg(feature = "tracing")]
+                if finished {
+                    debug!(frame = frame_count(world), "action finished");
+                }
+                !finished
+            };
+            if running {
+                reached += 1;
+            } else if let Some(runner_id) = runner_id {
+                token.finish_runner(*runner_id);
             }
+            running
         });
+        ParallelJobs::set_running_schedule(world, None);
+        // Runners not reached due to the budget are run first in the next frame.
+        runners.0.rotate_left(reached);
+        if let (Some(start), Some(mut stats)) = (start, world.get_resource_mut::<FlurxStats>()) {
+            stats.run_runners_time += start.elapsed();
+            stats.runners_finished += len - runners.0.len();
+            stats.runners.insert(runners.1, runners.0.len());
+        }
         world.insert_non_send_resource(runners);
     }
 }
 
+/// Cancels the reactor that `token` belongs to and sends [`ReactorFailed`].
+///
+/// `source` describes what panicked, e.g. the action name.
+pub(crate) fn fail_reactor(
+    world: &mut World,
+    source: &str,
+    token: &CancellationToken,
+    payload: Box<dyn Any + Send>,
+) {
+    let message = panic_message(payload.as_ref());
+    error!("{source} panicked: {message}");
+    token.cancel();
+    token.call_cancel_handles(world);
+    world.send_event(ReactorFailed {
+        entity: token.reactor_entity(),
+        message,
+    });
+}
+
+/// Returns the message of the panic payload.
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+#[inline]
+pub(crate) fn frame_count(world: &World) -> u32 {
+    world.get_resource::<FrameCount>().map_or(0, |frame| frame.0)
+}
+
 pub(crate) mod macros {
     macro_rules! output_combine {
         ($o1: expr, $o2: expr, $output: expr $(,)?) => {
@@ -158,15 +356,17 @@ pub(crate) mod macros {
 #[cfg(test)]
 mod tests {
     use bevy::app::Startup;
-    use bevy::prelude::{Commands, ResMut, Update, World};
+    use bevy::ecs::event::ManualEventReader;
+    use bevy::prelude::{Commands, default, Events, ResMut, Resource, Update, World};
     use bevy_test_helper::resource::count::Count;
     use bevy_test_helper::resource::DirectResourceControl;
 
-    use crate::action::wait;
-    use crate::prelude::{ActionSeed, CancellationToken, Reactor};
+    use crate::action::{once, wait};
+    use crate::FlurxPlugin;
+    use crate::prelude::{ActionSeed, CancellationToken, Reactor, ReactorFailed, RunnerBudget};
     use crate::runner::Runner;
     use crate::test_util::test;
-    use crate::tests::test_app;
+    use crate::tests::{test_app, test_app_with};
 
     struct TestCancelRunner {
         limit: usize,
@@ -235,4 +435,152 @@ mod tests {
             app.assert_resource_eq(Count(1));
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn catch_panic_in_action() {
+        let mut app = test_app_with(FlurxPlugin {
+            catch_panics: true,
+            ..default()
+        });
+        let entity = app.world.spawn(Reactor::schedule(|task| async move {
+            task.will(Update, wait::both(
+                test::count_on_cancelled(),
+                once::run(|| {
+                    panic!("failed to load script");
+                }),
+            )).await;
+            task.will(Update, once::run(|| {
+                unreachable!();
+            })).await;
+        })).id();
+        app.update();
+        app.assert_resource_eq(Count(1));
+        let failed = ManualEventReader::<ReactorFailed>::default()
+            .read(app.world.resource::<Events<ReactorFailed>>())
+            .cloned()
+            .collect::<Vec<_>>();
+        assert_eq!(failed, vec![ReactorFailed {
+            entity: Some(entity),
+            message: "failed to load script".to_string(),
+        }]);
+
+        app.update();
+        assert!(app.world.get_entity(entity).is_none());
+    }
+
+    #[test]
+    fn catch_panic_in_reactor() {
+        let mut app = test_app_with(FlurxPlugin {
+            catch_panics: true,
+            ..default()
+        });
+        let entity = app.world.spawn(Reactor::schedule(|task| async move {
+            task.will(Update, once::run(|| {})).await;
+            panic!("failed to parse dialogue");
+        })).id();
+        app.update();
+        let failed = ManualEventReader::<ReactorFailed>::default()
+            .read(app.world.resource::<Events<ReactorFailed>>())
+            .cloned()
+            .collect::<Vec<_>>();
+        assert_eq!(failed, vec![ReactorFailed {
+            entity: Some(entity),
+            message: "failed to parse dialogue".to_string(),
+        }]);
+        assert!(app.world.get_entity(entity).is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn not_catch_panic_by_default() {
+        let mut app = test_app();
+        app.world.spawn(Reactor::schedule(|task| async move {
+            task.will(Update, once::run(|| {
+                panic!("failed to load script");
+            })).await;
+        }));
+        app.update();
+    }
+
+    #[test]
+    fn run_runners_within_budget() {
+        let mut app = test_app_with(FlurxPlugin {
+            budget: RunnerBudget {
+                max_runners: Some(2),
+                max_time: None,
+            },
+            ..default()
+        });
+        for _ in 0..5 {
+            app.world.spawn(Reactor::schedule(|task| async move {
+                task.will(Update, once::run(|mut count: ResMut<Count>| {
+                    count.increment();
+                })).await;
+            }));
+        }
+        app.update();
+        app.assert_resource_eq(Count(2));
+        app.update();
+        app.assert_resource_eq(Count(4));
+        app.update();
+        app.assert_resource_eq(Count(5));
+    }
+
+    #[derive(Resource, Debug, Default, Eq, PartialEq)]
+    struct Counts([usize; 3]);
+
+    #[test]
+    fn resume_runners_round_robin() {
+        let mut app = test_app_with(FlurxPlugin {
+            budget: RunnerBudget {
+                max_runners: Some(2),
+                max_time: None,
+            },
+            ..default()
+        });
+        app.init_resource::<Counts>();
+        for i in 0..3 {
+            app.world.spawn(Reactor::schedule(move |task| async move {
+                task.will(Update, wait::until(move |mut counts: ResMut<Counts>| {
+                    counts.0[i] += 1;
+                    false
+                })).await;
+            }));
+        }
+        for _ in 0..3 {
+            app.update();
+        }
+        app.assert_resource_eq(Counts([2, 2, 2]));
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn emit_tracing_events() {
+        use bevy::utils::tracing::subscriber::with_default;
+
+        use crate::test_util::capture::CaptureSubscriber;
+
+        let subscriber = CaptureSubscriber::default();
+        let events = subscriber.events.clone();
+        with_default(subscriber, || {
+            let mut app = test_app();
+            app.world.spawn(Reactor::schedule(|task| async move {
+                task.will(Update, once::run(|| {})).await;
+            }));
+            app.world.spawn(Reactor::schedule(|task| async move {
+                task.will(Update, test::cancel()).await;
+            }));
+            app.update();
+        });
+
+        let events = events.lock().unwrap();
+        let count = |span: &str, message: &str| {
+            events.iter().filter(|(s, m)| *s == Some(span) && m == message).count()
+        };
+        assert_eq!(count("reactor", "reactor started"), 2);
+        assert_eq!(count("action", "action started"), 2);
+        assert_eq!(count("action", "action finished"), 2);
+        assert_eq!(count("reactor", "reactor finished"), 1);
+        assert_eq!(count("reactor", "reactor cancelled"), 1);
+    }
+}
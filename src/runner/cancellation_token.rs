@@ -1,10 +1,16 @@
 use std::cell::{Cell, RefCell};
+#[cfg(feature = "tracing")]
+use std::cell::OnceCell;
 use std::fmt::{Debug, Formatter};
 use std::rc::Rc;
 use std::sync::atomic::{AtomicU64, Ordering};
 
-use bevy::prelude::World;
+use bevy::prelude::{Entity, World};
 use bevy::utils::HashMap;
+#[cfg(feature = "tracing")]
+use bevy::utils::tracing::{debug, Id, Span};
+
+use crate::reactor::RunnerDebugInfo;
 
 
 /// The cancellation handler id assigned by [`CancellationToken`].
@@ -39,7 +45,12 @@ impl CancellationToken {
     /// Requests to cancel a [`Reactor`](crate::prelude::Reactor).
     #[inline]
     pub fn cancel(&self) {
-        self.0.is_cancellation_requested.set(true);
+        let _already_requested = self.0.is_cancellation_requested.replace(true);
+        #[cfg(feature = "tracing")]
+        // A finished reactor is also cancelled when it is despawned.
+        if !_already_requested && !self.finished_reactor() {
+            debug!(parent: self.span_id(), "reactor cancelled");
+        }
     }
 
     /// Returns `true` if cancellation has been requested for a [`Reactor`](crate::prelude::Reactor).
@@ -59,6 +70,85 @@ impl CancellationToken {
         }
     }
     
+    #[inline(always)]
+    pub(crate) fn set_owner(&self, owner: Entity) {
+        self.0.owner.set(Some(owner));
+    }
+
+    #[inline(always)]
+    pub(crate) fn add_scope(&self, in_scope: impl Fn(&World) -> bool + 'static) {
+        self.0.scopes.borrow_mut().push(Box::new(in_scope));
+    }
+
+    /// Returns `true` if the entity the [`Reactor`](crate::prelude::Reactor) is attached to has been despawned,
+    /// or if any of the scopes of the [`Reactor`](crate::prelude::Reactor) has been left.
+    ///
+    /// If it is, cancellation is also requested.
+    #[inline(always)]
+    pub(crate) fn out_of_scope(&self, world: &World) -> bool {
+        let out_of_scope = self.0.owner.get().is_some_and(|owner| !world.entities().contains(owner))
+            || self.0.scopes.borrow().iter().any(|in_scope| !in_scope(world));
+        if out_of_scope {
+            self.cancel();
+        }
+        out_of_scope
+    }
+
+    pub(crate) fn start_runner(&self, info: RunnerDebugInfo) -> u64 {
+        let id = self.0.runner_id.get();
+        self.0.runner_id.set(id + 1);
+        self.0.active_runners.borrow_mut().push((id, info));
+        self.0.active_runners_changed.set(true);
+        id
+    }
+
+    pub(crate) fn finish_runner(&self, id: u64) {
+        self.0.active_runners.borrow_mut().retain(|(runner_id, _)| *runner_id != id);
+        self.0.active_runners_changed.set(true);
+    }
+
+    /// Returns the runners currently registered by the [`Reactor`](crate::prelude::Reactor)
+    /// if they have changed since the last call.
+    pub(crate) fn take_changed_runners(&self) -> Option<Vec<RunnerDebugInfo>> {
+        self.0.active_runners_changed.replace(false).then(|| {
+            self
+                .0
+                .active_runners
+                .borrow()
+                .iter()
+                .map(|(_, info)| info.clone())
+                .collect()
+        })
+    }
+
+    #[inline(always)]
+    pub(crate) fn set_reactor_entity(&self, entity: Entity) {
+        self.0.reactor_entity.set(Some(entity));
+    }
+
+    #[inline(always)]
+    pub(crate) fn reactor_entity(&self) -> Option<Entity> {
+        self.0.reactor_entity.get()
+    }
+
+    #[cfg(feature = "tracing")]
+    #[inline]
+    pub(crate) fn set_span(&self, span: Span) {
+        let _ = self.0.span.set(span);
+    }
+
+    #[cfg(feature = "tracing")]
+    #[inline]
+    pub(crate) fn span(&self) -> Option<&Span> {
+        self.0.span.get()
+    }
+
+    #[cfg(feature = "tracing")]
+    #[inline]
+    pub(crate) fn span_id(&self) -> Option<Id> {
+        self.span().and_then(Span::id)
+    }
+
     #[inline(always)]
     pub(crate) fn set_finished(&self) {
         self.0.reactor_finished.set(true);
@@ -84,6 +174,14 @@ pub(crate) struct ReactorStatus {
     pub cancel_handles: RefCell<HashMap<CancellationId, Box<dyn FnOnce(&mut World)>>>,
     pub is_cancellation_requested: Cell<bool>,
     pub reactor_finished: Cell<bool>,
+    pub owner: Cell<Option<Entity>>,
+    pub reactor_entity: Cell<Option<Entity>>,
+    pub scopes: RefCell<Vec<Box<dyn Fn(&World) -> bool>>>,
+    pub runner_id: Cell<u64>,
+    pub active_runners: RefCell<Vec<(u64, RunnerDebugInfo)>>,
+    pub active_runners_changed: Cell<bool>,
+    #[cfg(feature = "tracing")]
+    pub span: OnceCell<Span>,
 }
 
 impl ReactorStatus{
@@ -101,6 +199,10 @@ impl Debug for ReactorStatus {
             .field("cancellation_id", &self.cancellation_id.load(Ordering::Relaxed))
             .field("is_cancellation_requested", &self.is_cancellation_requested.get())
             .field("reactor_finished", &self.reactor_finished.get())
+            .field("owner", &self.owner.get())
+            .field("reactor_entity", &self.reactor_entity.get())
+            .field("scopes", &self.scopes.borrow().len())
+            .field("active_runners", &self.active_runners.borrow())
             .finish()
     }
 }
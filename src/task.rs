@@ -32,7 +32,7 @@ impl ReactiveTask {
     /// use bevy::prelude::*;
     /// use bevy_flurx::prelude::*;
     /// let mut app = App::new();
-    /// app.add_plugins(FlurxPlugin);
+    /// app.add_plugins(FlurxPlugin::default());
     /// app.add_systems(Startup, |mut commands: Commands|{
     ///     commands.spawn(Reactor::schedule(|task| async move{
     ///         let count: u8 = task.will(Update, wait::output(|mut count: Local<u8>|{
@@ -69,7 +69,7 @@ impl ReactiveTask {
     /// use bevy_flurx::prelude::*;
     ///
     /// let mut app = App::new();
-    /// app.add_plugins(FlurxPlugin);
+    /// app.add_plugins(FlurxPlugin::default());
     /// app.add_systems(Startup, |mut commands: Commands|{
     ///     commands.spawn(Reactor::schedule(|task|async move{
     ///         let wait_event = task.run(Update, wait::event::comes::<AppExit>()).await;
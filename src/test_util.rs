@@ -1,4 +1,8 @@
 use std::future::Future;
+#[cfg(feature = "effect")]
+use std::sync::Mutex;
+#[cfg(feature = "effect")]
+use std::time::{Duration, Instant};
 
 use bevy::app::App;
 use bevy::prelude::World;
@@ -24,8 +28,120 @@ impl SpawnReactor for App {
     }
 }
 
+/// Returns the number of [`Reactor`]s in the world.
+pub fn reactor_count(app: &mut App) -> usize {
+    app.world.query::<&Reactor>().iter(&app.world).len()
+}
+
+/// Updates `app` until `finished` returns `true`, for the tests waiting on other threads.
+///
+/// Panics after 5 seconds.
+#[cfg(feature = "effect")]
+pub fn update_until(app: &mut App, mut finished: impl FnMut(&mut App) -> bool) {
+    let start = Instant::now();
+    while !finished(app) {
+        assert!(start.elapsed() < Duration::from_secs(5), "timed out");
+        app.update();
+        std::thread::sleep(Duration::from_millis(1));
+    }
+}
+
+/// Updates `app` until all the [`Reactor`]s have finished.
+#[cfg(feature = "effect")]
+pub fn update_until_finished(app: &mut App) {
+    update_until(app, |app| reactor_count(app) == 0);
+}
+
+/// Updates `app` until `value` is set, and takes it.
+#[cfg(feature = "effect")]
+pub fn update_until_some<T>(app: &mut App, value: &Mutex<Option<T>>) -> T {
+    let mut taken = None;
+    update_until(app, |_| {
+        taken = value.lock().unwrap().take();
+        taken.is_some()
+    });
+    taken.unwrap()
+}
+
+/// Captures the events emitted with the `tracing` feature.
+#[cfg(feature = "tracing")]
+pub mod capture {
+    use std::fmt::Debug;
+    use std::sync::{Arc, Mutex};
+
+    use bevy::utils::tracing::{Event, Id, Metadata, Subscriber};
+    use bevy::utils::tracing::field::{Field, Visit};
+    use bevy::utils::tracing::span::{Attributes, Record};
+
+    /// Pairs of the name of the span an event belongs to and the message of the event.
+    pub type Captured = Arc<Mutex<Vec<(Option<&'static str>, String)>>>;
+
+    #[derive(Default)]
+    pub struct CaptureSubscriber {
+        spans: Mutex<Vec<&'static str>>,
+        entered: Mutex<Vec<u64>>,
+        pub events: Captured,
+    }
+
+    impl CaptureSubscriber {
+        fn span_name(&self, id: &Id) -> Option<&'static str> {
+            self.spans.lock().unwrap().get(id.into_u64() as usize - 1).copied()
+        }
+    }
+
+    impl Subscriber for CaptureSubscriber {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut spans = self.spans.lock().unwrap();
+            spans.push(span.metadata().name());
+            Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let parent = if event.is_contextual() {
+                self.entered.lock().unwrap().last().map(|id| Id::from_u64(*id))
+            } else {
+                event.parent().cloned()
+            };
+            let mut message = MessageVisitor(String::new());
+            event.record(&mut message);
+            let span = parent.and_then(|id| self.span_name(&id));
+            self.events.lock().unwrap().push((span, message.0));
+        }
+
+        fn enter(&self, span: &Id) {
+            self.entered.lock().unwrap().push(span.into_u64());
+        }
+
+        fn exit(&self, span: &Id) {
+            let mut entered = self.entered.lock().unwrap();
+            if let Some(i) = entered.iter().rposition(|id| *id == span.into_u64()) {
+                entered.remove(i);
+            }
+        }
+    }
+
+    struct MessageVisitor(String);
+
+    impl Visit for MessageVisitor {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            if field.name() == "message" {
+                self.0 = format!("{value:?}");
+            }
+        }
+    }
+}
+
 pub mod test {
     use bevy::prelude::World;
+    use bevy_test_helper::resource::count::Count;
 
     use crate::prelude::{ActionSeed, CancellationToken, Runner};
 
@@ -43,4 +159,25 @@ pub mod test {
             true
         }
     }
-}
\ No newline at end of file
+
+    /// Never finishes, and increments [`Count`] when cancelled.
+    pub fn count_on_cancelled() -> ActionSeed {
+        ActionSeed::new(|_, _| {
+            TestCountOnCancelledRunner(false)
+        })
+    }
+
+    struct TestCountOnCancelledRunner(bool);
+
+    impl Runner for TestCountOnCancelledRunner {
+        fn run(&mut self, _: &mut World, token: &CancellationToken) -> bool {
+            if !self.0 {
+                self.0 = true;
+                token.register(|world| {
+                    world.resource_mut::<Count>().increment();
+                });
+            }
+            false
+        }
+    }
+}